@@ -0,0 +1,333 @@
+//! This module provides access to the asset files of the site, such as images,
+//! while pages are rendered.
+
+use crate::location::{Fragment, InternalPath};
+use anyhow::{anyhow, Context as _};
+use image::{imageops::FilterType, DynamicImage, ImageFormat};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::HashMap,
+    fmt::Write as _,
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
+
+/// The asset files of the site, as seen while rendering pages. Clones share
+/// the information read from image files.
+#[derive(Debug, Clone)]
+pub struct Assets {
+    /// The directory of the assets.
+    dir: PathBuf,
+    /// Widths in pixels of the thumbnails generated for every image wider
+    /// than them.
+    thumbnail_widths: Vec<u32>,
+    /// Information already read from image files, by path.
+    images: Arc<Mutex<HashMap<InternalPath, ImageInfo>>>,
+}
+
+impl Assets {
+    /// Creates the assets view over the given directory.
+    pub fn new<P>(dir: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        Self {
+            dir: dir.into(),
+            thumbnail_widths: Vec::new(),
+            images: Arc::default(),
+        }
+    }
+
+    /// Sets the widths in pixels of the thumbnails generated for every image
+    /// wider than them.
+    pub fn thumbnail_widths(mut self, widths: Vec<u32>) -> Self {
+        self.thumbnail_widths = widths;
+        self.images = Arc::default();
+        self
+    }
+
+    /// The directory of the assets.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The path of the file an internal path points to.
+    pub fn fs_path(&self, path: &InternalPath) -> PathBuf {
+        self.dir.join(path.to_fs_path())
+    }
+
+    /// Reads the dimensions of an image from its header and finds its resized
+    /// variants. A variant of `img/map.png` is named like `img/map-480w.png`,
+    /// where 480 is its width in pixels. Variants are either found in the
+    /// assets directory or generated from [`Assets::thumbnail_widths`]. The
+    /// information is read once per image.
+    pub fn image(&self, path: &InternalPath) -> anyhow::Result<ImageInfo> {
+        if let Some(info) = self.images.lock().unwrap().get(path) {
+            return Ok(info.clone());
+        }
+        let info = self.read_image(path)?;
+        self.images.lock().unwrap().insert(path.clone(), info.clone());
+        Ok(info)
+    }
+
+    /// Reads the information about an image from the files.
+    fn read_image(&self, path: &InternalPath) -> anyhow::Result<ImageInfo> {
+        let fs_path = self.fs_path(path);
+        if !fs_path.is_file() {
+            Err(anyhow!("Missing image file {}", fs_path.display()))?;
+        }
+        let (width, height) = image::ImageReader::open(&fs_path)
+            .and_then(|reader| reader.with_guessed_format())
+            .map_err(anyhow::Error::from)
+            .and_then(|reader| Ok(reader.into_dimensions()?))
+            .with_context(|| format!("Reading image {}", fs_path.display()))?;
+
+        let mut variants = Vec::new();
+        let name = path.fragments.last().map_or("", Fragment::as_str);
+        let (stem, ext) = name.rsplit_once('.').unwrap_or((name, ""));
+        if let Some(dir) = fs_path.parent() {
+            let entries = fs::read_dir(dir)
+                .with_context(|| format!("Opening dir {}", dir.display()))?;
+            for entry in entries {
+                let entry = entry.with_context(|| {
+                    format!("Reading dir {}", dir.display())
+                })?;
+                let file_name = entry.file_name();
+                let file_name = match file_name.to_str() {
+                    Some(file_name) => file_name,
+                    None => continue,
+                };
+                if let Some(width) = variant_width(file_name, stem, ext) {
+                    let mut variant = path.clone();
+                    variant.fragments.pop();
+                    variants.push(ImageVariant {
+                        path: variant.append(Fragment::new(file_name)?),
+                        width,
+                    });
+                }
+            }
+        }
+        for &thumb_width in &self.thumbnail_widths {
+            let exists =
+                variants.iter().any(|variant| variant.width == thumb_width);
+            if thumb_width < width && !exists {
+                let mut variant = path.clone();
+                variant.fragments.pop();
+                let file_name = format!(
+                    "{}-{}w.{}",
+                    stem,
+                    thumb_width,
+                    derivative_format(ext).1
+                );
+                variants.push(ImageVariant {
+                    path: variant.append(Fragment::new(file_name)?),
+                    width: thumb_width,
+                });
+            }
+        }
+        variants.sort_by_key(|variant| variant.width);
+
+        Ok(ImageInfo { width, height, variants })
+    }
+
+    /// Generates the thumbnails of an image which are not in the assets
+    /// directory into the output directory. Thumbnails already in the output
+    /// directory and newer than the image are kept. Thumbnails are also cached
+    /// in `cache_dir` by the hash of the image contents, so unchanged images
+    /// are not resized again even for a new output directory.
+    pub fn gen_thumbnails(
+        &self,
+        path: &InternalPath,
+        output_dir: &Path,
+        cache_dir: Option<&Path>,
+    ) -> anyhow::Result<()> {
+        let info = self.image(path)?;
+        let fs_path = self.fs_path(path);
+        let modified = fs::metadata(&fs_path)
+            .and_then(|metadata| metadata.modified())
+            .with_context(|| format!("Reading image {}", fs_path.display()))?;
+        let missing = info
+            .variants
+            .iter()
+            .filter(|variant| !self.fs_path(&variant.path).is_file())
+            .filter(|variant| {
+                let output_path = output_dir.join(variant.path.to_fs_path());
+                !is_newer(&output_path, modified)
+            })
+            .collect::<Vec<_>>();
+        if missing.is_empty() {
+            return Ok(());
+        }
+
+        let contents = fs::read(&fs_path)
+            .with_context(|| format!("Reading image {}", fs_path.display()))?;
+        let hash = Sha256::digest(&contents).iter().fold(
+            String::new(),
+            |mut hash, byte| {
+                let _ = write!(hash, "{:02x}", byte);
+                hash
+            },
+        );
+        let mut decoded = None::<DynamicImage>;
+
+        for variant in missing {
+            let output_path = output_dir.join(variant.path.to_fs_path());
+            if let Some(parent) = output_path.parent() {
+                fs::create_dir_all(parent).with_context(|| {
+                    format!("Creating dir {}", parent.display())
+                })?;
+            }
+            let ext = variant.path.fragments.last().map_or("", |name| {
+                name.as_str().rsplit_once('.').map_or("", |(_, ext)| ext)
+            });
+            let cache_path = cache_dir.map(|cache_dir| {
+                cache_dir.join(format!("{}-{}w.{}", hash, variant.width, ext))
+            });
+
+            if let Some(cache_path) =
+                cache_path.as_ref().filter(|cache_path| cache_path.is_file())
+            {
+                fs::copy(cache_path, &output_path).with_context(|| {
+                    format!(
+                        "Copying {} to {}",
+                        cache_path.display(),
+                        output_path.display()
+                    )
+                })?;
+                continue;
+            }
+
+            let image = match &mut decoded {
+                Some(image) => image,
+                None => decoded.insert(
+                    image::load_from_memory(&contents).with_context(|| {
+                        format!("Decoding image {}", fs_path.display())
+                    })?,
+                ),
+            };
+            let height = (u64::from(image.height()) * u64::from(variant.width)
+                / u64::from(image.width().max(1)))
+            .max(1) as u32;
+            let thumbnail =
+                image.resize_exact(variant.width, height, FilterType::Lanczos3);
+            let format = derivative_format(ext).0;
+            let thumbnail = match format {
+                ImageFormat::Jpeg => DynamicImage::from(thumbnail.to_rgb8()),
+                _ => thumbnail,
+            };
+
+            let target = cache_path.as_deref().unwrap_or(&output_path);
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent).with_context(|| {
+                    format!("Creating dir {}", parent.display())
+                })?;
+            }
+            thumbnail.save_with_format(target, format).with_context(|| {
+                format!("Writing thumbnail {}", target.display())
+            })?;
+            if target != output_path {
+                fs::copy(target, &output_path).with_context(|| {
+                    format!(
+                        "Copying {} to {}",
+                        target.display(),
+                        output_path.display()
+                    )
+                })?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Tests if a file exists and was modified after the given time.
+fn is_newer(path: &Path, time: SystemTime) -> bool {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .is_ok_and(|modified| modified >= time)
+}
+
+/// Information about an image file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageInfo {
+    /// Width in pixels.
+    pub width: u32,
+    /// Height in pixels.
+    pub height: u32,
+    /// Resized variants of the image, from the narrowest to the widest.
+    pub variants: Vec<ImageVariant>,
+}
+
+/// A resized variant of an image.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageVariant {
+    /// Path to the variant.
+    pub path: InternalPath,
+    /// Width in pixels.
+    pub width: u32,
+}
+
+/// The format and extension of the thumbnails of an image with the given
+/// extension: JPEG images stay JPEG, every other image becomes PNG.
+fn derivative_format(ext: &str) -> (ImageFormat, &str) {
+    match ext.to_ascii_lowercase().as_str() {
+        "jpg" | "jpeg" => (ImageFormat::Jpeg, ext),
+        _ => (ImageFormat::Png, "png"),
+    }
+}
+
+/// The width of a variant named like `{stem}-{width}w.{ext}`, if the given
+/// file name is one.
+fn variant_width(file_name: &str, stem: &str, ext: &str) -> Option<u32> {
+    let rest = file_name.strip_prefix(stem)?.strip_prefix('-')?;
+    let rest = if ext.is_empty() {
+        rest
+    } else {
+        rest.strip_suffix(ext)?.strip_suffix('.')?
+    };
+    let digits = rest.strip_suffix('w')?;
+    if digits.is_empty() || !digits.bytes().all(|ch| ch.is_ascii_digit()) {
+        return None;
+    }
+    digits.parse().ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::Assets;
+    use crate::location::InternalPath;
+    use std::fs;
+
+    #[test]
+    fn thumbnails_are_cached() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        let assets_dir = dir.join("assets");
+        let output_dir = dir.join("output");
+        let cache_dir = dir.join("cache");
+        fs::create_dir_all(assets_dir.join("img")).unwrap();
+        image::RgbImage::new(8, 4)
+            .save(assets_dir.join("img/map.png"))
+            .unwrap();
+
+        let assets = Assets::new(&assets_dir).thumbnail_widths(vec![4, 16]);
+        let path = InternalPath::parse("img/map.png").unwrap();
+        let info = assets.image(&path).unwrap();
+        assert_eq!(info.variants.len(), 1);
+        assert_eq!(info.variants[0].path.to_string(), "img/map-4w.png");
+
+        assets.gen_thumbnails(&path, &output_dir, Some(&cache_dir)).unwrap();
+        let output = output_dir.join("img/map-4w.png");
+        assert_eq!(image::image_dimensions(&output).unwrap(), (4, 2));
+        assert_eq!(fs::read_dir(&cache_dir).unwrap().count(), 1);
+
+        fs::remove_file(&output).unwrap();
+        assets.gen_thumbnails(&path, &output_dir, Some(&cache_dir)).unwrap();
+        assert!(output.is_file());
+
+        fs::remove_file(assets_dir.join("img/map.png")).unwrap();
+        assert_eq!(assets.clone().image(&path).unwrap(), info);
+    }
+}
@@ -0,0 +1,192 @@
+//! This module provides the bibliography of the site: the works that pages can
+//! cite, usually imported from BibTeX files.
+
+use anyhow::{anyhow, Context as _};
+use biblatex::ChunksExt;
+use std::{collections::BTreeMap, fs, path::Path};
+use url::Url;
+
+/// The style of citations and reference lists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum CitationStyle {
+    /// Citations such as "(Smith 2001)", references sorted by author.
+    #[default]
+    AuthorDate,
+    /// Citations such as "[1]", references numbered in order of citation.
+    Numeric,
+}
+
+/// An author of a work.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Author {
+    /// Family name (surname), including prefixes such as "van".
+    pub family: String,
+    /// Given names.
+    pub given: String,
+}
+
+/// A work that can be cited.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Reference {
+    /// Authors of the work, in order.
+    pub authors: Vec<Author>,
+    /// Title of the work.
+    pub title: Option<String>,
+    /// Year of publication.
+    pub year: Option<String>,
+    /// Journal or book in which the work was published.
+    pub container: Option<String>,
+    /// Publisher of the work.
+    pub publisher: Option<String>,
+    /// Pages of the work in its container.
+    pub pages: Option<String>,
+    /// Where the work can be found online.
+    pub url: Option<Url>,
+}
+
+impl Reference {
+    /// The short form of the authors used in author-date citations, such as
+    /// "Smith", "Smith & Doe" or "Smith et al.".
+    pub fn short_authors(&self) -> String {
+        match &self.authors[..] {
+            [] => String::from("Anonymous"),
+            [first] => first.family.clone(),
+            [first, second] => format!("{} & {}", first.family, second.family),
+            [first, ..] => format!("{} et al.", first.family),
+        }
+    }
+
+    /// The year, or "n.d." if unknown.
+    pub fn year_or_nd(&self) -> &str {
+        self.year.as_deref().unwrap_or("n.d.")
+    }
+}
+
+/// The site-wide bibliography, indexed by citation key.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Bibliography {
+    /// Style of citations and reference lists.
+    pub style: CitationStyle,
+    /// The works, indexed by citation key.
+    pub references: BTreeMap<String, Reference>,
+}
+
+impl Bibliography {
+    /// Creates an empty bibliography with the given style.
+    pub fn new(style: CitationStyle) -> Self {
+        Self { style, references: BTreeMap::new() }
+    }
+
+    /// Finds a work by its citation key.
+    pub fn get(&self, key: &str) -> Option<&Reference> {
+        self.references.get(key)
+    }
+
+    /// Inserts a work under the given citation key, replacing any previous
+    /// one.
+    pub fn insert<S>(&mut self, key: S, reference: Reference)
+    where
+        S: Into<String>,
+    {
+        self.references.insert(key.into(), reference);
+    }
+
+    /// Loads every entry of a local BibTeX (`.bib`) file into this
+    /// bibliography.
+    pub fn load_bibtex<P>(&mut self, path: P) -> anyhow::Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path).with_context(|| {
+            format!("Reading bibliography {}", path.display())
+        })?;
+        self.parse_bibtex(&contents)
+            .with_context(|| format!("Parsing bibliography {}", path.display()))
+    }
+
+    /// Parses BibTeX source and inserts every entry into this bibliography.
+    pub fn parse_bibtex(&mut self, source: &str) -> anyhow::Result<()> {
+        let bibliography = biblatex::Bibliography::parse(source)
+            .map_err(|error| anyhow!("{}", error))?;
+
+        for entry in bibliography.iter() {
+            let field = |name: &str| {
+                entry
+                    .get(name)
+                    .map(|chunks| chunks.format_verbatim())
+                    .filter(|value| !value.is_empty())
+            };
+
+            let authors = entry
+                .author()
+                .unwrap_or_default()
+                .into_iter()
+                .map(|person| {
+                    let family = if person.prefix.is_empty() {
+                        person.name
+                    } else {
+                        format!("{} {}", person.prefix, person.name)
+                    };
+                    Author { family, given: person.given_name }
+                })
+                .collect();
+            let year = field("year").or_else(|| {
+                field("date").map(|date| {
+                    date.split(['-', '/']).next().unwrap_or_default().to_owned()
+                })
+            });
+            let url = field("url")
+                .map(|url| {
+                    Url::parse(&url).with_context(|| {
+                        format!("Invalid URL in entry {}", entry.key)
+                    })
+                })
+                .transpose()?;
+
+            let reference = Reference {
+                authors,
+                title: field("title"),
+                year,
+                container: field("journaltitle")
+                    .or_else(|| field("journal"))
+                    .or_else(|| field("booktitle")),
+                publisher: field("publisher"),
+                pages: field("pages"),
+                url,
+            };
+            self.insert(entry.key.clone(), reference);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Bibliography;
+
+    #[test]
+    fn parse_bibtex() {
+        let mut bibliography = Bibliography::default();
+        bibliography
+            .parse_bibtex(
+                "@book{tolkien1937, author = {J. R. R. Tolkien}, title = {The \
+                 Hobbit}, year = 1937, publisher = {Allen & Unwin}}
+                 @article{smith2001, author = {Smith, John and Doe, Jane}, \
+                 title = {Sound changes}, date = {2001-05}, journal = \
+                 {Linguistics}}",
+            )
+            .unwrap();
+
+        let hobbit = bibliography.get("tolkien1937").unwrap();
+        assert_eq!(hobbit.short_authors(), "Tolkien");
+        assert_eq!(hobbit.year_or_nd(), "1937");
+        assert_eq!(hobbit.publisher.as_deref(), Some("Allen & Unwin"));
+
+        let article = bibliography.get("smith2001").unwrap();
+        assert_eq!(article.short_authors(), "Smith & Doe");
+        assert_eq!(article.year_or_nd(), "2001");
+        assert_eq!(article.container.as_deref(), Some("Linguistics"));
+    }
+}
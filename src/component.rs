@@ -5,8 +5,30 @@ pub mod img;
 pub mod table;
 pub mod list;
 pub mod audio;
-
-use crate::{location::InternalPath, site::Site};
+pub mod video;
+mod media;
+pub mod style;
+pub mod raw;
+pub mod code;
+pub mod math;
+pub mod note;
+pub mod cite;
+pub mod infobox;
+pub mod template;
+pub mod gloss;
+pub mod ipa;
+pub mod lang;
+pub mod glossary;
+pub mod details;
+pub mod numbered;
+
+use crate::{
+    asset::Assets,
+    component::style::Attributes,
+    location::{Id, InternalLoc, InternalPath},
+    page::PageState,
+    site::Site,
+};
 use std::{borrow::Cow, fmt, rc::Rc, sync::Arc};
 
 fn html_escape(ch: char) -> Option<&'static str> {
@@ -38,12 +60,18 @@ pub struct InlineComponent;
 pub struct Context<'loc, 'site> {
     location: &'loc InternalPath,
     site: &'site Site,
+    state: &'loc PageState,
 }
 
 impl<'loc, 'site> Context<'loc, 'site> {
-    /// Creates the context from the current page's location and the given site.
-    pub(crate) fn new(location: &'loc InternalPath, site: &'site Site) -> Self {
-        Self { location, site }
+    /// Creates the context from the current page's location, the given site and
+    /// the state of the page rendering.
+    pub(crate) fn new(
+        location: &'loc InternalPath,
+        site: &'site Site,
+        state: &'loc PageState,
+    ) -> Self {
+        Self { location, site, state }
     }
 
     /// The location of the current page.
@@ -51,6 +79,71 @@ impl<'loc, 'site> Context<'loc, 'site> {
         self.location
     }
 
+    /// The site being rendered.
+    pub fn site(self) -> &'site Site {
+        self.site
+    }
+
+    /// Takes the next number of the given counter in the current page, such as
+    /// `"equation"`, starting at 1. If an ID is given, the number can be
+    /// referred to through [`Context::number_of`].
+    pub fn next_number(self, counter: &'static str, id: Option<&Id>) -> u32 {
+        self.state.next_number(counter, id)
+    }
+
+    /// Reports an error found while rendering, such as a reference to
+    /// something that does not exist. The generation of the page fails with
+    /// this error. Returns the formatting error that must be propagated.
+    pub fn report<E>(self, error: E) -> fmt::Error
+    where
+        E: Into<anyhow::Error>,
+    {
+        self.state.report(error.into());
+        fmt::Error
+    }
+
+    /// Requires a style sheet to be included in the head of the current page.
+    /// The same style sheet is only included once.
+    pub fn require_style(self, style: &'static str) {
+        self.state.require_style(style);
+    }
+
+    /// Requires a script file to be included in the head of the current page.
+    /// The generator writes the source to the given path of the output only
+    /// if some page requires it.
+    pub fn require_script(self, path: &'static str, source: &'static str) {
+        self.state.require_script(path, source);
+    }
+
+    /// Requires a font file of the assets to be declared in the head of the
+    /// current page under the given family name.
+    pub fn require_font(self, family: &Id, path: &InternalPath) {
+        self.state.require_font(family, path);
+    }
+
+    /// The asset files of the site, if they are checked in this rendering.
+    pub fn assets(self) -> Option<&'loc Assets> {
+        self.state.assets()
+    }
+
+    /// Tests if this is the final rendering pass, the one actually written.
+    /// References to numbers not known in the final pass are errors, while in
+    /// the first pass they may still be defined further in the page.
+    pub fn is_final_pass(self) -> bool {
+        self.state.is_final_pass()
+    }
+
+    /// Finds the number given to an ID by [`Context::next_number`], either in
+    /// the current page or in another page of the site.
+    pub fn number_of(self, loc: &InternalLoc) -> Option<u32> {
+        let id = loc.id.as_ref()?;
+        if loc.path.eq_index(self.location) {
+            self.state.number_of(id)
+        } else {
+            self.state.foreign_number_of(&loc.path, id, self.site)
+        }
+    }
+
     /// Creates a renderer over a component from this context. The `Display`
     /// trait can be used on the renderer.
     pub fn renderer<T>(self, component: T) -> Renderer<'loc, 'site, T>
@@ -97,6 +190,26 @@ pub trait Component: fmt::Debug {
     /// Renders this component given the context and the output formatter.
     fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result;
 
+    /// Renders this component with extra attributes on its root element. By
+    /// default, the component is wrapped in the given element, such as
+    /// `span`, which carries the attributes. Components rendering a single
+    /// element of their own override this to write the attributes into it.
+    fn to_html_with(
+        &self,
+        fmt: &mut fmt::Formatter,
+        ctx: Context,
+        attrs: &Attributes,
+        wrapper: &str,
+    ) -> fmt::Result {
+        write!(
+            fmt,
+            "<{0}{1}>{2}</{0}>",
+            wrapper,
+            attrs.display(ctx, ""),
+            ctx.renderer(self)
+        )
+    }
+
     /// Ensures this component can be used as a blocking component.
     fn blocking(self) -> Blocking<Self>
     where
@@ -105,6 +218,14 @@ pub trait Component: fmt::Debug {
         Blocking(self)
     }
 
+    /// Wraps this component so that custom attributes can be attached to it.
+    fn styled(self) -> style::Styled<Self, Self::Kind>
+    where
+        Self: Sized,
+    {
+        style::Styled::new(self)
+    }
+
     /// Transforms this component into a dynamic dispatch component.
     fn to_dyn(self) -> DynComponent<Self::Kind>
     where
@@ -123,6 +244,16 @@ where
     fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
         (**self).to_html(fmt, ctx)
     }
+
+    fn to_html_with(
+        &self,
+        fmt: &mut fmt::Formatter,
+        ctx: Context,
+        attrs: &Attributes,
+        wrapper: &str,
+    ) -> fmt::Result {
+        (**self).to_html_with(fmt, ctx, attrs, wrapper)
+    }
 }
 
 impl<T> Component for Box<T>
@@ -134,6 +265,16 @@ where
     fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
         (**self).to_html(fmt, ctx)
     }
+
+    fn to_html_with(
+        &self,
+        fmt: &mut fmt::Formatter,
+        ctx: Context,
+        attrs: &Attributes,
+        wrapper: &str,
+    ) -> fmt::Result {
+        (**self).to_html_with(fmt, ctx, attrs, wrapper)
+    }
 }
 
 impl<T> Component for Rc<T>
@@ -145,6 +286,16 @@ where
     fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
         (**self).to_html(fmt, ctx)
     }
+
+    fn to_html_with(
+        &self,
+        fmt: &mut fmt::Formatter,
+        ctx: Context,
+        attrs: &Attributes,
+        wrapper: &str,
+    ) -> fmt::Result {
+        (**self).to_html_with(fmt, ctx, attrs, wrapper)
+    }
 }
 
 impl<T> Component for Arc<T>
@@ -156,6 +307,16 @@ where
     fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
         (**self).to_html(fmt, ctx)
     }
+
+    fn to_html_with(
+        &self,
+        fmt: &mut fmt::Formatter,
+        ctx: Context,
+        attrs: &Attributes,
+        wrapper: &str,
+    ) -> fmt::Result {
+        (**self).to_html_with(fmt, ctx, attrs, wrapper)
+    }
 }
 
 impl<'cow, T> Component for Cow<'cow, T>
@@ -168,6 +329,16 @@ where
     fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
         (**self).to_html(fmt, ctx)
     }
+
+    fn to_html_with(
+        &self,
+        fmt: &mut fmt::Formatter,
+        ctx: Context,
+        attrs: &Attributes,
+        wrapper: &str,
+    ) -> fmt::Result {
+        (**self).to_html_with(fmt, ctx, attrs, wrapper)
+    }
 }
 
 impl<T> Component for Vec<T>
@@ -196,6 +367,19 @@ where
         }
         Ok(())
     }
+
+    fn to_html_with(
+        &self,
+        fmt: &mut fmt::Formatter,
+        ctx: Context,
+        attrs: &Attributes,
+        wrapper: &str,
+    ) -> fmt::Result {
+        if let Some(component) = self {
+            component.to_html_with(fmt, ctx, attrs, wrapper)?;
+        }
+        Ok(())
+    }
 }
 
 impl Component for str {
@@ -242,3 +426,37 @@ where
         self.0.to_html(fmt, ctx)
     }
 }
+
+/// The site, location and state of a page from which tests create a
+/// [`Context`].
+#[cfg(test)]
+pub(crate) struct TestContext {
+    /// The site being rendered.
+    pub site: Site,
+    /// The location of the page.
+    pub location: InternalPath,
+    /// The state of the page rendering.
+    pub state: PageState,
+}
+
+#[cfg(test)]
+impl TestContext {
+    /// Creates the context of a page at the given path of a default site.
+    pub fn new(location: &str) -> Self {
+        Self::with_site(Site::default(), location)
+    }
+
+    /// Creates the context of a page at the given path of the given site.
+    pub fn with_site(site: Site, location: &str) -> Self {
+        Self {
+            site,
+            location: InternalPath::parse(location).unwrap(),
+            state: PageState::default(),
+        }
+    }
+
+    /// The context of a component rendering in this page.
+    pub fn ctx(&self) -> Context<'_, '_> {
+        Context::new(&self.location, &self.site, &self.state)
+    }
+}
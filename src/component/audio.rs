@@ -1,7 +1,14 @@
-//! This module provides an audio component for the encyclopedia.
+//! This module provides audio components for the encyclopedia.
 
 use crate::{
-    component::{Component, Context, InlineComponent},
+    component::{
+        media::{write_fallback, write_sources, MediaKind},
+        BlockComponent,
+        Component,
+        Context,
+        DynComponent,
+        InlineComponent,
+    },
     location::Location,
 };
 use std::fmt;
@@ -14,10 +21,179 @@ impl Component for Audio {
     type Kind = InlineComponent;
 
     fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        write!(fmt, "<audio controls preload=\"metadata\" class=\"audio\">")?;
+        write_sources(
+            fmt,
+            ctx,
+            MediaKind::Audio,
+            std::slice::from_ref(&self.0),
+        )?;
+        write_fallback(fmt, ctx, std::slice::from_ref(&self.0))?;
+        write!(fmt, "</audio>")
+    }
+}
+
+/// A block audio player, with the locations of the audio files, in different
+/// formats, and an optional transcript.
+#[derive(Debug, Clone)]
+pub struct AudioBlock {
+    /// Locations of the audio files, each in a different format, in order of
+    /// preference.
+    pub sources: Vec<Location>,
+    /// Transcript of the recording, shown collapsed below the player.
+    pub transcript: Option<DynComponent<BlockComponent>>,
+    /// Label of the collapsed transcript, `"Transcript"` by default.
+    pub transcript_label: DynComponent<InlineComponent>,
+}
+
+impl AudioBlock {
+    /// Creates an audio player with a single source.
+    pub fn new(src: Location) -> Self {
+        Self {
+            sources: vec![src],
+            transcript: None,
+            transcript_label: String::from("Transcript").to_dyn(),
+        }
+    }
+
+    /// Appends an alternative source, in a different format.
+    pub fn source(mut self, src: Location) -> Self {
+        self.sources.push(src);
+        self
+    }
+
+    /// Sets the transcript of the recording.
+    pub fn transcript<T>(mut self, transcript: T) -> Self
+    where
+        T: Component<Kind = BlockComponent> + Send + Sync + 'static,
+    {
+        self.transcript = Some(transcript.to_dyn());
+        self
+    }
+
+    /// Sets the label of the collapsed transcript, such as in the language of
+    /// the site.
+    pub fn transcript_label<T>(mut self, label: T) -> Self
+    where
+        T: Component<Kind = InlineComponent> + Send + Sync + 'static,
+    {
+        self.transcript_label = label.to_dyn();
+        self
+    }
+}
+
+impl Component for AudioBlock {
+    type Kind = BlockComponent;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        write!(
+            fmt,
+            "<div class=\"audio-block\"><audio controls preload=\"metadata\">"
+        )?;
+        write_sources(fmt, ctx, MediaKind::Audio, &self.sources)?;
+        write_fallback(fmt, ctx, &self.sources)?;
+        write!(fmt, "</audio>")?;
+        if let Some(transcript) = &self.transcript {
+            write!(fmt, "<details class=\"audio-transcript\">")?;
+            write!(
+                fmt,
+                "<summary>{}</summary>{}</details>",
+                ctx.renderer(&self.transcript_label),
+                ctx.renderer(transcript)
+            )?;
+        }
+        write!(fmt, "</div>")
+    }
+}
+
+/// An inline button playing a short recording, such as the pronunciation of
+/// a transcription.
+#[derive(Debug, Clone)]
+pub struct PlayButton {
+    /// Locations of the audio files, each in a different format, in order of
+    /// preference.
+    pub sources: Vec<Location>,
+    /// Accessible label of the button.
+    pub label: String,
+}
+
+impl PlayButton {
+    /// Creates a play button with a single source.
+    pub fn new(src: Location) -> Self {
+        Self { sources: vec![src], label: String::from("Play") }
+    }
+
+    /// Appends an alternative source, in a different format.
+    pub fn source(mut self, src: Location) -> Self {
+        self.sources.push(src);
+        self
+    }
+
+    /// Sets the accessible label of the button.
+    pub fn label<S>(mut self, label: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.label = label.into();
+        self
+    }
+}
+
+impl Component for PlayButton {
+    type Kind = InlineComponent;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        write!(fmt, "<span class=\"play-audio\"><audio preload=\"none\">")?;
+        write_sources(fmt, ctx, MediaKind::Audio, &self.sources)?;
+        write_fallback(fmt, ctx, &self.sources)?;
         write!(
             fmt,
-            "<audio controls src={}>No browser support for audio.</audio>",
-            ctx.renderer(&self.0)
+            "</audio><button type=\"button\" title=\"{0}\" aria-label=\"{0}\" \
+             onclick=\"this.previousElementSibling.play()\">▶</button></span>",
+            ctx.renderer(&self.label),
         )
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{Audio, AudioBlock, PlayButton};
+    use crate::{
+        component::{text::Paragraph, TestContext},
+        location::Location,
+    };
+
+    #[test]
+    fn play_button_sources() {
+        let test = TestContext::new("langs/index.html");
+        let ctx = test.ctx();
+        let button = PlayButton::new(Location::internal("audio/a.ogg"))
+            .source(Location::internal("audio/a.mp3"));
+        assert_eq!(
+            ctx.renderer(&button).to_string(),
+            "<span class=\"play-audio\"><audio preload=\"none\"><source \
+             src=\"../audio/a.ogg\" type=\"audio/ogg\"><source \
+             src=\"../audio/a.mp3\" type=\"audio/mpeg\"><a \
+             href=\"../audio/a.ogg\">../audio/a.ogg</a></audio><button \
+             type=\"button\" title=\"Play\" aria-label=\"Play\" \
+             onclick=\"this.previousElementSibling.play()\">▶</button></span>"
+        );
+
+        let audio = Audio(Location::internal("audio/a.mp3"));
+        assert_eq!(
+            ctx.renderer(&audio).to_string(),
+            "<audio controls preload=\"metadata\" class=\"audio\"><source \
+             src=\"../audio/a.mp3\" type=\"audio/mpeg\"><a \
+             href=\"../audio/a.mp3\">../audio/a.mp3</a></audio>"
+        );
+
+        let block = AudioBlock::new(Location::internal("audio/a.mp3"))
+            .transcript(Paragraph("Lupus est."))
+            .transcript_label("Transcrição");
+        assert!(ctx.renderer(&block).to_string().ends_with(
+            "</audio><details \
+             class=\"audio-transcript\"><summary>Transcrição</summary><p \
+             class=\"paragraph\">Lupus est.</p></details></div>"
+        ));
+    }
+}
@@ -0,0 +1,187 @@
+//! This module exports citation components. Works are looked up in the site's
+//! [`Bibliography`](crate::bib::Bibliography), and the cited ones are listed in
+//! a "References" section after every other section of the page.
+
+use crate::{
+    bib::{CitationStyle, Reference},
+    component::{BlockComponent, Component, Context, InlineComponent},
+};
+use anyhow::anyhow;
+use std::fmt;
+
+/// A citation of one or more works of the bibliography, by their keys.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Cite {
+    /// Keys of the cited works.
+    pub keys: Vec<String>,
+    /// Where in the work, such as "p. 23". Only used with a single key.
+    pub locator: Option<String>,
+}
+
+impl Cite {
+    /// Cites a single work.
+    pub fn new<S>(key: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self { keys: vec![key.into()], locator: None }
+    }
+
+    /// Cites a single work at a specific place, such as "p. 23".
+    pub fn at<S, L>(key: S, locator: L) -> Self
+    where
+        S: Into<String>,
+        L: Into<String>,
+    {
+        Self { keys: vec![key.into()], locator: Some(locator.into()) }
+    }
+}
+
+impl Component for Cite {
+    type Kind = InlineComponent;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        let bibliography = &ctx.site().bibliography;
+        let (open, separator, close) = match bibliography.style {
+            CitationStyle::AuthorDate => ("(", "; ", ")"),
+            CitationStyle::Numeric => ("[", ", ", "]"),
+        };
+
+        write!(fmt, "<span class=\"citation\">{}", open)?;
+        for (i, key) in self.keys.iter().enumerate() {
+            let reference = bibliography.get(key).ok_or_else(|| {
+                ctx.report(anyhow!("Unknown citation key {}", key))
+            })?;
+            let number = ctx.state.cite(key);
+            if i > 0 {
+                fmt.write_str(separator)?;
+            }
+            write!(fmt, "<a href=\"#ref-{}\">", ctx.renderer(key))?;
+            match bibliography.style {
+                CitationStyle::AuthorDate => write!(
+                    fmt,
+                    "{} {}",
+                    ctx.renderer(&reference.short_authors()),
+                    ctx.renderer(reference.year_or_nd()),
+                )?,
+                CitationStyle::Numeric => write!(fmt, "{}", number)?,
+            }
+            write!(fmt, "</a>")?;
+        }
+        if let Some(locator) = &self.locator {
+            write!(fmt, ", {}", ctx.renderer(locator))?;
+        }
+        write!(fmt, "{}</span>", close)?;
+        Ok(())
+    }
+}
+
+/// The list of works cited in the current page. Internal use.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct References;
+
+impl Component for References {
+    type Kind = BlockComponent;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        let bibliography = &ctx.site().bibliography;
+        let mut cited = ctx
+            .state
+            .citations()
+            .into_iter()
+            .filter_map(|key| {
+                let reference = bibliography.get(&key)?;
+                Some((key, reference))
+            })
+            .collect::<Vec<_>>();
+
+        let tag = match bibliography.style {
+            CitationStyle::AuthorDate => {
+                cited.sort_by(|(_, left), (_, right)| {
+                    (&left.authors, &left.year)
+                        .cmp(&(&right.authors, &right.year))
+                });
+                "ul"
+            },
+            CitationStyle::Numeric => "ol",
+        };
+
+        write!(fmt, "<{} class=\"references\">", tag)?;
+        for (key, reference) in cited {
+            write!(
+                fmt,
+                "<li id=\"ref-{}\" class=\"reference\">",
+                ctx.renderer(&key)
+            )?;
+            write_reference(fmt, ctx, reference, bibliography.style)?;
+            write!(fmt, "</li>")?;
+        }
+        write!(fmt, "</{}>", tag)?;
+        Ok(())
+    }
+}
+
+/// Writes a formatted entry of the reference list.
+fn write_reference(
+    fmt: &mut fmt::Formatter,
+    ctx: Context,
+    reference: &Reference,
+    style: CitationStyle,
+) -> fmt::Result {
+    for (i, author) in reference.authors.iter().enumerate() {
+        if i > 0 {
+            fmt.write_str(if i + 1 == reference.authors.len() {
+                " &amp; "
+            } else {
+                ", "
+            })?;
+        }
+        match style {
+            CitationStyle::AuthorDate => write!(
+                fmt,
+                "{}, {}",
+                ctx.renderer(&author.family),
+                ctx.renderer(&author.given)
+            )?,
+            CitationStyle::Numeric => write!(
+                fmt,
+                "{} {}",
+                ctx.renderer(&author.given),
+                ctx.renderer(&author.family)
+            )?,
+        }
+    }
+
+    if let CitationStyle::AuthorDate = style {
+        write!(fmt, " ({}).", ctx.renderer(reference.year_or_nd()))?;
+    } else if !reference.authors.is_empty() {
+        fmt.write_str(".")?;
+    }
+    if let Some(title) = &reference.title {
+        write!(fmt, " <i class=\"italic\">{}</i>.", ctx.renderer(title))?;
+    }
+    if let Some(container) = &reference.container {
+        write!(fmt, " {}", ctx.renderer(container))?;
+        if let Some(pages) = &reference.pages {
+            write!(fmt, ", pp. {}", ctx.renderer(pages))?;
+        }
+        fmt.write_str(".")?;
+    }
+    if let Some(publisher) = &reference.publisher {
+        write!(fmt, " {}", ctx.renderer(publisher))?;
+        if let CitationStyle::Numeric = style {
+            write!(fmt, ", {}", ctx.renderer(reference.year_or_nd()))?;
+        }
+        fmt.write_str(".")?;
+    } else if let CitationStyle::Numeric = style {
+        write!(fmt, " {}.", ctx.renderer(reference.year_or_nd()))?;
+    }
+    if let Some(url) = &reference.url {
+        write!(
+            fmt,
+            " <a href=\"{0}\" class=\"link\">{0}</a>",
+            ctx.renderer(url.as_str())
+        )?;
+    }
+    Ok(())
+}
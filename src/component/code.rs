@@ -0,0 +1,214 @@
+//! This module exports a code block component, with syntax highlighting.
+
+use crate::component::{BlockComponent, Component, Context};
+use anyhow::Context as _;
+use std::{collections::BTreeSet, fmt, fs, path::Path, sync::OnceLock};
+use syntect::{
+    highlighting::ThemeSet,
+    html::{
+        css_for_theme_with_class_style,
+        line_tokens_to_classed_spans,
+        ClassStyle,
+    },
+    parsing::{ParseState, ScopeStack, SyntaxReference, SyntaxSet},
+    util::LinesWithEndings,
+};
+
+/// Prefix of the CSS classes of highlighted tokens.
+const CLASS_PREFIX: &str = "hl-";
+
+/// Class style used for the highlighted tokens.
+const CLASS_STYLE: ClassStyle =
+    ClassStyle::SpacedPrefixed { prefix: CLASS_PREFIX };
+
+/// The default syntax definitions, loaded only once.
+fn syntax_set() -> &'static SyntaxSet {
+    static SYNTAX_SET: OnceLock<SyntaxSet> = OnceLock::new();
+    SYNTAX_SET.get_or_init(SyntaxSet::load_defaults_newlines)
+}
+
+/// A block of source code. The code is highlighted into CSS classes prefixed
+/// by `hl-` at generation time; see [`CodeBlock::theme_css`] for a stylesheet.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct CodeBlock {
+    /// The source code.
+    pub code: String,
+    /// Language of the code, either by name or file extension, such as
+    /// `"Rust"` or `"py"`. Not highlighted if `None` or unknown.
+    pub lang: Option<String>,
+    /// Whether line numbers are displayed.
+    pub line_numbers: bool,
+    /// Lines highlighted with the `highlighted` class, counting from 1.
+    pub highlighted: BTreeSet<usize>,
+}
+
+impl CodeBlock {
+    /// Creates a code block with the given code and language, without line
+    /// numbers and highlighted lines.
+    pub fn new<S, L>(code: S, lang: L) -> Self
+    where
+        S: Into<String>,
+        L: Into<String>,
+    {
+        Self { code: code.into(), lang: Some(lang.into()), ..Self::default() }
+    }
+
+    /// Loads the code from a file on disk. The language is inferred from the
+    /// file extension.
+    pub fn from_file<P>(path: P) -> anyhow::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let code = fs::read_to_string(path)
+            .with_context(|| format!("Reading code file {}", path.display()))?;
+        let lang = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_owned());
+        Ok(Self { code, lang, ..Self::default() })
+    }
+
+    /// Enables line numbers. Returns the modified code block.
+    pub fn with_line_numbers(mut self) -> Self {
+        self.line_numbers = true;
+        self
+    }
+
+    /// Highlights the given line, counting from 1. Returns the modified code
+    /// block.
+    pub fn highlight(mut self, line: usize) -> Self {
+        self.highlighted.insert(line);
+        self
+    }
+
+    /// Creates a CSS stylesheet for the classes of highlighted tokens, from
+    /// one of the default themes, such as `"InspiredGitHub"` or
+    /// `"base16-ocean.dark"`.
+    pub fn theme_css(theme: &str) -> anyhow::Result<String> {
+        let themes = ThemeSet::load_defaults();
+        let theme = themes
+            .themes
+            .get(theme)
+            .with_context(|| format!("Unknown code theme {}", theme))?;
+        Ok(css_for_theme_with_class_style(theme, CLASS_STYLE)?)
+    }
+
+    /// Finds the syntax definition of this block's language.
+    fn syntax(&self) -> Option<&'static SyntaxReference> {
+        let lang = self.lang.as_ref()?;
+        syntax_set().find_syntax_by_token(lang)
+    }
+
+    /// Writes the opening of a line.
+    fn write_line_start(
+        &self,
+        fmt: &mut fmt::Formatter,
+        number: usize,
+    ) -> fmt::Result {
+        write!(fmt, "<span class=\"code-line")?;
+        if self.highlighted.contains(&number) {
+            write!(fmt, " highlighted")?;
+        }
+        write!(fmt, "\"")?;
+        if self.line_numbers {
+            write!(fmt, " data-line=\"{}\"", number)?;
+        }
+        write!(fmt, ">")
+    }
+}
+
+impl Component for CodeBlock {
+    type Kind = BlockComponent;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        write!(fmt, "<div class=\"code-block")?;
+        if self.line_numbers {
+            write!(fmt, " numbered")?;
+        }
+        write!(fmt, "\"><pre class=\"code")?;
+        if let Some(lang) = &self.lang {
+            write!(fmt, " language-{}", ctx.renderer(lang))?;
+        }
+        write!(fmt, "\"><code>")?;
+
+        let syntax = self.syntax();
+        let mut parse_state = syntax.map(ParseState::new);
+        let mut stack = ScopeStack::new();
+
+        for (i, line) in LinesWithEndings::from(&self.code).enumerate() {
+            self.write_line_start(fmt, i + 1)?;
+            let content = line.strip_suffix('\n').unwrap_or(line);
+            let content = content.strip_suffix('\r').unwrap_or(content);
+
+            match &mut parse_state {
+                Some(parse_state) => {
+                    for scope in stack.as_slice() {
+                        write!(fmt, "<span class=\"")?;
+                        for (j, atom) in
+                            scope.build_string().split('.').enumerate()
+                        {
+                            if j > 0 {
+                                fmt.write_str(" ")?;
+                            }
+                            write!(fmt, "{}{}", CLASS_PREFIX, atom)?;
+                        }
+                        write!(fmt, "\">")?;
+                    }
+                    let mut ops = parse_state
+                        .parse_line(line, syntax_set())
+                        .map_err(|error| ctx.report(error))?;
+                    for (index, _) in &mut ops {
+                        *index = (*index).min(content.len());
+                    }
+                    let (html, _) = line_tokens_to_classed_spans(
+                        content,
+                        &ops,
+                        CLASS_STYLE,
+                        &mut stack,
+                    )
+                    .map_err(|error| ctx.report(error))?;
+                    fmt.write_str(&html)?;
+                    for _ in stack.as_slice() {
+                        fmt.write_str("</span>")?;
+                    }
+                },
+                None => content.to_html(fmt, ctx)?,
+            }
+
+            writeln!(fmt, "</span>")?;
+        }
+
+        write!(fmt, "</code></pre></div>")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::CodeBlock;
+    use crate::component::TestContext;
+
+    #[test]
+    fn lines_are_wrapped() {
+        let test = TestContext::new("scripts/index.html");
+        let ctx = test.ctx();
+        let code = CodeBlock::new("/* a\nb */\nx < 1", "c")
+            .with_line_numbers()
+            .highlight(2);
+        let html = ctx.renderer(&code).to_string();
+        let lines = html.split('\n').collect::<Vec<_>>();
+
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with(
+            "<div class=\"code-block numbered\"><pre class=\"code \
+             language-c\"><code><span class=\"code-line\" data-line=\"1\">"
+        ));
+        assert!(lines[1].starts_with(
+            "<span class=\"code-line highlighted\" data-line=\"2\"><span \
+             class=\"hl-source hl-c\"><span class=\"hl-comment"
+        ));
+        assert!(lines[2].contains("&lt;"));
+        assert_eq!(lines[3], "</code></pre></div>");
+    }
+}
@@ -0,0 +1,195 @@
+//! This module exports components hiding part of their contents until the
+//! reader asks for it: collapsible details and tabs.
+
+use crate::component::{
+    style::Attributes,
+    BlockComponent,
+    Component,
+    Context,
+    DynComponent,
+    InlineComponent,
+};
+use std::fmt;
+
+/// The counter used to name the tabs of a page.
+const TABS_COUNTER: &str = "tabs";
+
+/// The style showing only the panel of the selected tab. Without it, every
+/// panel is shown below its label.
+const TABS_STYLE: &str = concat!(
+    ".tabs { display: flex; flex-wrap: wrap; } ",
+    ".tab-input { position: absolute; opacity: 0; } ",
+    ".tab-label { order: 0; padding: 0.25em 1em; cursor: pointer; } ",
+    ".tab-panel { order: 1; width: 100%; display: none; } ",
+    ".tab-input:checked + .tab-label { font-weight: bold; } ",
+    ".tab-input:focus-visible + .tab-label { outline: 2px solid; } ",
+    ".tab-input:checked + .tab-label + .tab-panel { display: block; }",
+);
+
+/// A collapsible block: a summary always shown, and a body shown when the
+/// summary is clicked.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Details<S, B>
+where
+    S: Component<Kind = InlineComponent>,
+    B: Component<Kind = BlockComponent>,
+{
+    /// The summary, always shown.
+    pub summary: S,
+    /// The body, shown on demand.
+    pub body: B,
+    /// Whether the body is shown at first.
+    pub open: bool,
+}
+
+impl<S, B> Details<S, B>
+where
+    S: Component<Kind = InlineComponent>,
+    B: Component<Kind = BlockComponent>,
+{
+    /// Creates a block collapsed at first.
+    pub fn new(summary: S, body: B) -> Self {
+        Self { summary, body, open: false }
+    }
+
+    /// Shows the body at first.
+    pub fn open(mut self) -> Self {
+        self.open = true;
+        self
+    }
+}
+
+impl<S, B> Component for Details<S, B>
+where
+    S: Component<Kind = InlineComponent>,
+    B: Component<Kind = BlockComponent>,
+{
+    type Kind = BlockComponent;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        self.to_html_with(fmt, ctx, &Attributes::default(), "div")
+    }
+
+    fn to_html_with(
+        &self,
+        fmt: &mut fmt::Formatter,
+        ctx: Context,
+        attrs: &Attributes,
+        _wrapper: &str,
+    ) -> fmt::Result {
+        write!(fmt, "<details{}", attrs.display(ctx, "details"))?;
+        if self.open {
+            write!(fmt, " open")?;
+        }
+        write!(fmt, "><summary>{}", ctx.renderer(&self.summary))?;
+        write!(fmt, "</summary>{}</details>", ctx.renderer(&self.body))?;
+        Ok(())
+    }
+}
+
+/// A tab of [`Tabs`].
+#[derive(Debug, Clone)]
+pub struct Tab {
+    /// The label of the tab.
+    pub label: DynComponent<InlineComponent>,
+    /// The body shown when the tab is selected.
+    pub body: DynComponent<BlockComponent>,
+}
+
+/// Several bodies of which only the one of the selected tab is shown, such as
+/// dialect variants of the same table. Needs no script: tabs are radio
+/// buttons styled as labels.
+#[derive(Debug, Clone, Default)]
+pub struct Tabs {
+    /// The tabs, the first one selected at first.
+    pub tabs: Vec<Tab>,
+}
+
+impl Tabs {
+    /// Creates tabs without any tab.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a tab.
+    pub fn tab<L, B>(mut self, label: L, body: B) -> Self
+    where
+        L: Component<Kind = InlineComponent> + Send + Sync + 'static,
+        B: Component<Kind = BlockComponent> + Send + Sync + 'static,
+    {
+        self.tabs.push(Tab { label: label.to_dyn(), body: body.to_dyn() });
+        self
+    }
+}
+
+impl Component for Tabs {
+    type Kind = BlockComponent;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        self.to_html_with(fmt, ctx, &Attributes::default(), "div")
+    }
+
+    fn to_html_with(
+        &self,
+        fmt: &mut fmt::Formatter,
+        ctx: Context,
+        attrs: &Attributes,
+        _wrapper: &str,
+    ) -> fmt::Result {
+        ctx.require_style(TABS_STYLE);
+        let name = format!("tabs-{}", ctx.next_number(TABS_COUNTER, None));
+        write!(fmt, "<div{}>", attrs.display(ctx, "tabs"))?;
+        for (i, tab) in self.tabs.iter().enumerate() {
+            write!(
+                fmt,
+                "<input type=\"radio\" name=\"{name}\" id=\"{name}-{i}\" \
+                 class=\"tab-input\"{checked}><label for=\"{name}-{i}\" \
+                 class=\"tab-label\">{label}</label>",
+                name = name,
+                i = i + 1,
+                checked = if i == 0 { " checked" } else { "" },
+                label = ctx.renderer(&tab.label),
+            )?;
+            write!(
+                fmt,
+                "<div class=\"tab-panel\">{}</div>",
+                ctx.renderer(&tab.body)
+            )?;
+        }
+        write!(fmt, "</div>")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Details, Tabs};
+    use crate::component::{text::Paragraph, TestContext};
+
+    #[test]
+    fn details_and_tabs() {
+        let test = TestContext::new("langs/index.html");
+        let ctx = test.ctx();
+        let details = Details::new("Paradigm", Paragraph("lupus")).open();
+        assert_eq!(
+            ctx.renderer(&details).to_string(),
+            "<details class=\"details\" open><summary>Paradigm</summary><p \
+             class=\"paragraph\">lupus</p></details>"
+        );
+
+        let tabs = Tabs::new()
+            .tab("North", Paragraph("lupus"))
+            .tab("South", Paragraph("lupos"));
+        assert_eq!(
+            ctx.renderer(&tabs).to_string(),
+            "<div class=\"tabs\"><input type=\"radio\" name=\"tabs-1\" \
+             id=\"tabs-1-1\" class=\"tab-input\" checked><label \
+             for=\"tabs-1-1\" class=\"tab-label\">North</label><div \
+             class=\"tab-panel\"><p class=\"paragraph\">lupus</p></div><input \
+             type=\"radio\" name=\"tabs-1\" id=\"tabs-1-2\" \
+             class=\"tab-input\"><label for=\"tabs-1-2\" \
+             class=\"tab-label\">South</label><div class=\"tab-panel\"><p \
+             class=\"paragraph\">lupos</p></div></div>"
+        );
+    }
+}
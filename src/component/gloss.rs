@@ -0,0 +1,276 @@
+//! This module exports interlinear glosses, following the Leipzig glossing
+//! rules: the words of an example are aligned with their morpheme-by-morpheme
+//! glosses, and a free translation follows.
+
+use crate::{
+    component::{
+        numbered::NumberRef,
+        text::SmallCaps,
+        BlockComponent,
+        Component,
+        Context,
+        InlineComponent,
+    },
+    location::{Id, InternalLoc},
+};
+use anyhow::anyhow;
+use std::fmt;
+
+/// The counter used to number examples.
+const EXAMPLE_COUNTER: &str = "example";
+
+/// The style aligning the words of a gloss with their glosses.
+const GLOSS_STYLE: &str = concat!(
+    ".gloss { display: flex; gap: 1em; margin: 1em 0; } ",
+    ".gloss-words { display: flex; flex-wrap: wrap; gap: 0.25em 1em; } ",
+    ".gloss-word { display: inline-flex; flex-direction: column; } ",
+    ".gloss-source { font-style: italic; } ",
+    ".gloss-translation { margin: 0.5em 0 0; }",
+);
+
+/// An interlinear gloss of an example. Each line is split into words by
+/// whitespace, and the words of every line are aligned with the words of the
+/// source line. In the gloss lines, runs of two capitals or more, such as
+/// `NOM` in `wolf-NOM.SG`, are abbreviations and are displayed in small
+/// capitals. Single capitals, such as in `I`, are kept as they are.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct InterlinearGloss {
+    /// The example, in the original language.
+    pub source: String,
+    /// The transliteration of the source, if written in another script.
+    pub transliteration: Option<String>,
+    /// Morpheme-by-morpheme gloss lines.
+    pub glosses: Vec<String>,
+    /// The free translation of the example.
+    pub translation: String,
+    /// ID that references the example, used by [`GlossRef`].
+    pub id: Option<Id>,
+    /// Whether the example is numbered.
+    pub numbered: bool,
+}
+
+impl InterlinearGloss {
+    /// Creates a numbered example without gloss lines.
+    pub fn new<S, T>(source: S, translation: T) -> Self
+    where
+        S: Into<String>,
+        T: Into<String>,
+    {
+        Self {
+            source: source.into(),
+            transliteration: None,
+            glosses: Vec::new(),
+            translation: translation.into(),
+            id: None,
+            numbered: true,
+        }
+    }
+
+    /// Sets the transliteration of the source.
+    pub fn transliteration<S>(mut self, transliteration: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.transliteration = Some(transliteration.into());
+        self
+    }
+
+    /// Adds a gloss line.
+    pub fn gloss<S>(mut self, line: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.glosses.push(line.into());
+        self
+    }
+
+    /// Sets the ID referencing the example.
+    pub fn id(mut self, id: Id) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Does not number the example.
+    pub fn unnumbered(mut self) -> Self {
+        self.numbered = false;
+        self
+    }
+
+    /// Splits a line into words, reporting lines not aligned with the source.
+    fn words<'line>(
+        &self,
+        line: &'line str,
+        source_len: usize,
+        ctx: Context,
+    ) -> Result<Vec<&'line str>, fmt::Error> {
+        let words = line.split_whitespace().collect::<Vec<_>>();
+        if words.len() != source_len {
+            Err(ctx.report(anyhow!(
+                "Invalid gloss: line {:?} has {} words, but source {:?} has {}",
+                line,
+                words.len(),
+                self.source,
+                source_len
+            )))?;
+        }
+        Ok(words)
+    }
+}
+
+impl Component for InterlinearGloss {
+    type Kind = BlockComponent;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        let source = self.source.split_whitespace().collect::<Vec<_>>();
+        let transliteration = match &self.transliteration {
+            Some(line) => Some(self.words(line, source.len(), ctx)?),
+            None => None,
+        };
+        let glosses = self
+            .glosses
+            .iter()
+            .map(|line| self.words(line, source.len(), ctx))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        ctx.require_style(GLOSS_STYLE);
+        write!(fmt, "<div class=\"gloss\"")?;
+        if let Some(id) = &self.id {
+            write!(fmt, " id=\"{}\"", ctx.renderer(id))?;
+        }
+        write!(fmt, ">")?;
+        if self.numbered {
+            write!(
+                fmt,
+                "<span class=\"gloss-number\">({})</span>",
+                ctx.next_number(EXAMPLE_COUNTER, self.id.as_ref())
+            )?;
+        }
+        write!(fmt, "<div class=\"gloss-body\"><div class=\"gloss-words\">")?;
+        for (i, word) in source.iter().enumerate() {
+            write!(
+                fmt,
+                "<div class=\"gloss-word\"><span \
+                 class=\"gloss-source\">{}</span>",
+                ctx.renderer(*word)
+            )?;
+            if let Some(transliteration) = &transliteration {
+                write!(
+                    fmt,
+                    "<span class=\"gloss-transliteration\">{}</span>",
+                    ctx.renderer(transliteration[i])
+                )?;
+            }
+            for line in &glosses {
+                write!(
+                    fmt,
+                    "<span class=\"gloss-line\">{}</span>",
+                    ctx.renderer(GlossWord(line[i]))
+                )?;
+            }
+            write!(fmt, "</div>")?;
+        }
+        write!(
+            fmt,
+            "</div><p class=\"gloss-translation\">‘{}’</p></div></div>",
+            ctx.renderer(self.translation.as_str())
+        )?;
+        Ok(())
+    }
+}
+
+/// A word of a gloss line, with its abbreviations in small capitals. Single
+/// capitals are not abbreviations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct GlossWord<'word>(&'word str);
+
+impl<'word> Component for GlossWord<'word> {
+    type Kind = InlineComponent;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        let mut rest = self.0;
+        while let Some(ch) = rest.chars().next() {
+            let is_letter = ch.is_alphabetic();
+            let end = rest
+                .find(|ch: char| ch.is_alphabetic() != is_letter)
+                .unwrap_or(rest.len());
+            let (run, after) = rest.split_at(end);
+            if is_letter
+                && run.chars().count() >= 2
+                && run.chars().all(char::is_uppercase)
+            {
+                write!(
+                    fmt,
+                    "{}",
+                    ctx.renderer(SmallCaps(run.to_lowercase().as_str()))
+                )?;
+            } else {
+                write!(fmt, "{}", ctx.renderer(run))?;
+            }
+            rest = after;
+        }
+        Ok(())
+    }
+}
+
+/// A reference to a numbered example, displayed as its number and linking to
+/// it. The example may be in another page.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GlossRef(pub InternalLoc);
+
+impl Component for GlossRef {
+    type Kind = InlineComponent;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        let reference = NumberRef::new(EXAMPLE_COUNTER, self.0.clone());
+        reference.to_html(fmt, ctx)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{GlossRef, InterlinearGloss};
+    use crate::{
+        component::TestContext,
+        location::{Id, InternalLoc},
+    };
+    use std::fmt::Write;
+
+    #[test]
+    fn aligned_words() {
+        let test = TestContext::new("langs/lat/index.html");
+        let ctx = test.ctx();
+        let gloss = InterlinearGloss::new("lupus currit", "The wolf runs.")
+            .gloss("wolf-NOM.SG I.run-3SG")
+            .id(Id::new("wolf-runs").unwrap());
+        assert_eq!(
+            ctx.renderer(&gloss).to_string(),
+            "<div class=\"gloss\" id=\"wolf-runs\"><span \
+             class=\"gloss-number\">(1)</span><div class=\"gloss-body\"><div \
+             class=\"gloss-words\"><div class=\"gloss-word\"><span \
+             class=\"gloss-source\">lupus</span><span \
+             class=\"gloss-line\">wolf-<span \
+             class=\"small-caps\">nom</span>.<span \
+             class=\"small-caps\">sg</span></span></div><div \
+             class=\"gloss-word\"><span \
+             class=\"gloss-source\">currit</span><span \
+             class=\"gloss-line\">I.run-3<span \
+             class=\"small-caps\">sg</span></span></div></div><p \
+             class=\"gloss-translation\">‘The wolf runs.’</p></div></div>"
+        );
+        assert_eq!(
+            test.state.number_of(&Id::new("wolf-runs").unwrap()),
+            Some(1)
+        );
+        let reference =
+            GlossRef(InternalLoc::parse("langs/lat#wolf-runs").unwrap());
+        assert_eq!(
+            ctx.renderer(&reference).to_string(),
+            "<a href=\"#wolf-runs\" class=\"example-ref\">(1)</a>"
+        );
+
+        let ragged = InterlinearGloss::new("lupus currit", "").gloss("wolf");
+        let mut html = String::new();
+        assert!(write!(html, "{}", ctx.renderer(&ragged)).is_err());
+        assert!(test.state.take_error().is_some());
+    }
+}
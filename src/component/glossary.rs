@@ -0,0 +1,139 @@
+//! This module exports components related to the glossary of the site.
+
+use crate::component::{BlockComponent, Component, Context, InlineComponent};
+use anyhow::anyhow;
+use std::fmt;
+
+/// An abbreviation, with its expansion shown when hovering it. The expansion
+/// is taken from the glossary of the site if not given.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Abbr {
+    /// The abbreviation, as displayed.
+    pub abbr: String,
+    /// The expansion of the abbreviation.
+    pub title: Option<String>,
+}
+
+impl Abbr {
+    /// Creates an abbreviation expanded by the glossary of the site.
+    pub fn new<S>(abbr: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self { abbr: abbr.into(), title: None }
+    }
+
+    /// Creates an abbreviation with the given expansion.
+    pub fn with_title<S, T>(abbr: S, title: T) -> Self
+    where
+        S: Into<String>,
+        T: Into<String>,
+    {
+        Self { abbr: abbr.into(), title: Some(title.into()) }
+    }
+}
+
+impl Component for Abbr {
+    type Kind = InlineComponent;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        let title = match &self.title {
+            Some(title) => title,
+            None => match ctx.site().glossary.get(&self.abbr) {
+                Some(entry) => &entry.definition,
+                None => Err(ctx.report(anyhow!(
+                    "Abbreviation {:?} is not in the glossary",
+                    self.abbr
+                )))?,
+            },
+        };
+        write!(
+            fmt,
+            "<abbr title=\"{}\" class=\"abbr\">{}</abbr>",
+            ctx.renderer(title),
+            ctx.renderer(&self.abbr)
+        )?;
+        Ok(())
+    }
+}
+
+/// Every entry of the glossary of the site, in alphabetical order. This is the
+/// body of the generated glossary page. Each term is anchored by the ID given
+/// by [`Glossary::ids`](crate::glossary::Glossary::ids).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct GlossaryList;
+
+impl Component for GlossaryList {
+    type Kind = BlockComponent;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        let glossary = &ctx.site().glossary;
+        let ids = glossary.ids();
+        write!(fmt, "<dl class=\"definition-list glossary\">")?;
+        for (term, entry) in glossary.sorted() {
+            write!(fmt, "<dt id=\"{}\">", ctx.renderer(&ids[term]))?;
+            match &entry.location {
+                Some(location) => write!(
+                    fmt,
+                    "<a href=\"{}\" class=\"link\">{}</a>",
+                    ctx.renderer(location),
+                    ctx.renderer(term)
+                )?,
+                None => write!(fmt, "{}", ctx.renderer(term))?,
+            }
+            write!(fmt, "</dt><dd>{}</dd>", ctx.renderer(&entry.definition))?;
+        }
+        write!(fmt, "</dl>")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Abbr, GlossaryList};
+    use crate::{
+        component::TestContext,
+        glossary::GlossaryEntry,
+        location::InternalLoc,
+        site::Site,
+    };
+    use std::fmt::Write;
+
+    #[test]
+    fn abbreviations_and_list() {
+        let mut site = Site::default();
+        site.glossary.insert("NOM", GlossaryEntry::new("nominative"));
+        site.glossary.insert(
+            "Ablaut",
+            GlossaryEntry::new("Vowel alternation")
+                .location(InternalLoc::parse("grammar#ablaut").unwrap()),
+        );
+        site.glossary.insert("ablaut", GlossaryEntry::new("Apophony"));
+        let test = TestContext::with_site(site, "glossary.html");
+        let ctx = test.ctx();
+
+        assert_eq!(
+            ctx.renderer(Abbr::new("NOM")).to_string(),
+            "<abbr title=\"nominative\" class=\"abbr\">NOM</abbr>"
+        );
+        assert_eq!(
+            ctx.renderer(Abbr::with_title("SG", "singular")).to_string(),
+            "<abbr title=\"singular\" class=\"abbr\">SG</abbr>"
+        );
+        let mut html = String::new();
+        assert!(write!(html, "{}", ctx.renderer(Abbr::new("ACC"))).is_err());
+        assert_eq!(
+            test.state.take_error().unwrap().to_string(),
+            "Abbreviation \"ACC\" is not in the glossary"
+        );
+
+        assert_eq!(
+            ctx.renderer(GlossaryList).to_string(),
+            "<dl class=\"definition-list glossary\"><dt id=\"ablaut\"><a \
+             href=\"grammar#ablaut\" class=\"link\">Ablaut</a></dt><dd>Vowel \
+             alternation</dd><dt \
+             id=\"ablaut-2\">ablaut</dt><dd>Apophony</dd><dt \
+             id=\"nom\">NOM</dt><dd>nominative</dd></dl>"
+        );
+    }
+}
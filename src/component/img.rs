@@ -1,7 +1,13 @@
 //! This module exports image-related components.
 
 use crate::{
-    component::{BlockComponent, Component, Context, InlineComponent},
+    component::{
+        BlockComponent,
+        Component,
+        Context,
+        DynComponent,
+        InlineComponent,
+    },
     location::Location,
 };
 use std::fmt;
@@ -20,12 +26,48 @@ impl Component for Image {
     type Kind = BlockComponent;
 
     fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        let info = match (&self.src, ctx.assets()) {
+            (Location::Internal(loc), Some(assets)) => {
+                let info = assets
+                    .image(&loc.path)
+                    .map_err(|error| ctx.report(error))?;
+                ctx.state.use_image(&loc.path);
+                Some(info)
+            },
+            _ => None,
+        };
+
         write!(
             fmt,
-            "<img src=\"{}\" alt=\"{}\" class=\"image\">",
+            "<img src=\"{}\" alt=\"{}\"",
             ctx.renderer(&self.src),
             ctx.renderer(&self.alt),
         )?;
+        if let Some(info) = info {
+            write!(
+                fmt,
+                " width=\"{}\" height=\"{}\"",
+                info.width, info.height
+            )?;
+            if !info.variants.is_empty() {
+                write!(fmt, " srcset=\"")?;
+                for variant in &info.variants {
+                    write!(
+                        fmt,
+                        "{} {}w, ",
+                        ctx.renderer(&variant.path),
+                        variant.width
+                    )?;
+                }
+                write!(
+                    fmt,
+                    "{0} {1}w\" sizes=\"(max-width: {1}px) 100vw, {1}px\"",
+                    ctx.renderer(&self.src),
+                    info.width,
+                )?;
+            }
+        }
+        write!(fmt, " loading=\"lazy\" decoding=\"async\" class=\"image\">")?;
         Ok(())
     }
 }
@@ -59,3 +101,184 @@ where
         Ok(())
     }
 }
+
+/// Style of the galleries: the grid and the lightbox shown when an image is
+/// selected, which only needs the `:target` selector. The selected item itself
+/// becomes the lightbox, so that its image and caption are not repeated.
+const GALLERY_STYLE: &str = concat!(
+    ".gallery{display:grid;gap:0.5em;",
+    "grid-template-columns:repeat(var(--gallery-columns),1fr)}",
+    ".gallery-item{margin:0}",
+    ".gallery-item img{width:100%;height:auto}",
+    ".gallery-close,.gallery-full{display:none}",
+    ".gallery-item:target{position:fixed;inset:0;z-index:100;display:flex;",
+    "flex-direction:column;align-items:center;justify-content:center;",
+    "background:rgba(0,0,0,0.85);color:#fff;text-align:center}",
+    ".gallery-item:target .gallery-close{display:block;position:absolute;",
+    "inset:0}",
+    ".gallery-item:target .gallery-open,",
+    ".gallery-item:target figcaption{position:relative}",
+    ".gallery-item:target img{max-width:90vw;max-height:80vh;width:auto}",
+    ".gallery-item:target .gallery-full{display:inline;color:inherit}",
+);
+
+/// A grid of images with captions. Selecting an image shows it in a lightbox,
+/// with a link to the full-size file.
+#[derive(Debug, Clone)]
+pub struct Gallery {
+    /// Number of columns of the grid.
+    pub columns: u32,
+    /// Images of the gallery, in order.
+    pub items: Vec<GalleryItem>,
+}
+
+impl Gallery {
+    /// Creates an empty gallery with the given number of columns.
+    pub fn new(columns: u32) -> Self {
+        Self { columns, items: Vec::new() }
+    }
+
+    /// Appends an image with its caption.
+    pub fn item<L>(mut self, img: Image, caption: L) -> Self
+    where
+        L: Component<Kind = InlineComponent> + Send + Sync + 'static,
+    {
+        self.items.push(GalleryItem { img, caption: caption.to_dyn() });
+        self
+    }
+}
+
+impl Default for Gallery {
+    fn default() -> Self {
+        Self::new(4)
+    }
+}
+
+impl Component for Gallery {
+    type Kind = BlockComponent;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        ctx.require_style(GALLERY_STYLE);
+        let number = ctx.next_number("gallery", None);
+
+        write!(
+            fmt,
+            "<div class=\"gallery\" id=\"gallery-{}\" \
+             style=\"--gallery-columns: {}\">",
+            number,
+            self.columns.max(1),
+        )?;
+        for (i, item) in self.items.iter().enumerate() {
+            write!(
+                fmt,
+                "<figure class=\"gallery-item\" id=\"gallery-{0}-{1}\"><a \
+                 href=\"#gallery-{0}-{1}\" class=\"gallery-open\">{2}</a><a \
+                 href=\"#gallery-{0}\" class=\"gallery-close\" \
+                 aria-label=\"Close\"></a><figcaption \
+                 class=\"gallery-caption\">{3} <a href=\"{4}\" \
+                 class=\"gallery-full\">⤢</a></figcaption></figure>",
+                number,
+                i + 1,
+                ctx.renderer(&item.img),
+                ctx.renderer(&item.caption),
+                ctx.renderer(&item.img.src),
+            )?;
+        }
+        write!(fmt, "</div>")?;
+        Ok(())
+    }
+}
+
+/// An image of a [`Gallery`] with its caption.
+#[derive(Debug, Clone)]
+pub struct GalleryItem {
+    /// Image data.
+    pub img: Image,
+    /// The caption component.
+    pub caption: DynComponent<InlineComponent>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Gallery, Image};
+    use crate::{
+        asset::Assets,
+        component::{Component, TestContext},
+        location::{InternalPath, Location},
+        page::{Page, PageState, RenderPage},
+        site::Site,
+    };
+    use std::{fmt::Write as _, fs};
+
+    #[test]
+    fn dimensions_and_variants() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        fs::create_dir_all(dir.join("img")).unwrap();
+        image::RgbImage::new(8, 4).save(dir.join("img/map.png")).unwrap();
+        image::RgbImage::new(4, 2).save(dir.join("img/map-4w.png")).unwrap();
+
+        let test = TestContext {
+            state: PageState::with_assets(Some(Assets::new(dir))),
+            ..TestContext::new("langs/index.html")
+        };
+        let ctx = test.ctx();
+        let image = Image {
+            src: Location::internal("img/map.png"),
+            alt: String::from("Map"),
+        };
+        assert_eq!(
+            ctx.renderer(&image).to_string(),
+            "<img src=\"../img/map.png\" alt=\"Map\" width=\"8\" height=\"4\" \
+             srcset=\"../img/map-4w.png 4w, ../img/map.png 8w\" \
+             sizes=\"(max-width: 8px) 100vw, 8px\" loading=\"lazy\" \
+             decoding=\"async\" class=\"image\">"
+        );
+
+        let missing = Image {
+            src: Location::internal("img/none.png"),
+            alt: String::from("None"),
+        };
+        let mut html = String::new();
+        assert!(write!(html, "{}", ctx.renderer(&missing)).is_err());
+        assert!(test.state.take_error().is_some());
+    }
+
+    #[test]
+    fn gallery_style_once() {
+        let site = Site::default();
+        let location = InternalPath::parse("scripts/index.html").unwrap();
+        let gallery = || {
+            Gallery::new(3).item(
+                Image {
+                    src: Location::internal("img/a.png"),
+                    alt: String::from("A"),
+                },
+                "Letter A",
+            )
+        };
+        let page = Page {
+            title: String::from("Scripts"),
+            body: vec![gallery(), gallery()].to_dyn(),
+            sections: Vec::new(),
+            lang: None,
+        };
+        let mut html = Vec::new();
+        RenderPage { page: &page, location: &location, site: &site }
+            .write_to(&mut html)
+            .unwrap();
+        let html = String::from_utf8(html).unwrap();
+        assert_eq!(html.matches("<style>").count(), 1);
+        assert!(html.contains("</title><style>.gallery{"));
+        assert!(html.contains(
+            "<figure class=\"gallery-item\" id=\"gallery-2-1\"><a \
+             href=\"#gallery-2-1\" class=\"gallery-open\"><img \
+             src=\"../img/a.png\" alt=\"A\" loading=\"lazy\" \
+             decoding=\"async\" class=\"image\"></a><a href=\"#gallery-2\" \
+             class=\"gallery-close\" aria-label=\"Close\"></a><figcaption \
+             class=\"gallery-caption\">Letter A <a href=\"../img/a.png\" \
+             class=\"gallery-full\">⤢</a></figcaption></figure></div>"
+        ));
+        assert_eq!(html.matches("<img").count(), 2);
+    }
+}
@@ -0,0 +1,248 @@
+//! This module exports the infobox component: the summary box shown aside of
+//! articles about languages, places, people, etc.
+
+use crate::component::{
+    img::Image,
+    style::Attributes,
+    BlockComponent,
+    Component,
+    Context,
+    DynComponent,
+    InlineComponent,
+};
+use std::fmt;
+
+/// The style floating infoboxes to the right of the article, or showing them
+/// across the whole width on narrow screens.
+const INFOBOX_STYLE: &str = concat!(
+    ".infobox { float: right; clear: right; width: 22em; max-width: 100%; ",
+    "margin: 0 0 1em 1em; padding: 0.5em; border: 1px solid #a2a9b1; ",
+    "box-sizing: border-box; } ",
+    ".infobox-title { font-weight: bold; text-align: center; } ",
+    ".infobox-image img { max-width: 100%; height: auto; } ",
+    "@media (max-width: 40em) { .infobox { float: none; width: auto; ",
+    "margin: 0 0 1em; } }",
+);
+
+/// A summary box floated aside of the article. Without styling, it reads as a
+/// linear list of labels and values.
+#[derive(Debug, Clone)]
+pub struct Infobox {
+    /// Title of the infobox, usually the name of the subject.
+    pub title: DynComponent<InlineComponent>,
+    /// Image shown below the title.
+    pub image: Option<Image>,
+    /// Groups of rows always shown.
+    pub groups: Vec<InfoboxGroup>,
+    /// Groups of rows shown only when the "more" part is expanded.
+    pub more: Vec<InfoboxGroup>,
+    /// Label of the collapsible "more" part, `"More"` by default.
+    pub more_label: DynComponent<InlineComponent>,
+}
+
+impl Infobox {
+    /// Creates an empty infobox with the given title.
+    pub fn new<T>(title: T) -> Self
+    where
+        T: Component<Kind = InlineComponent> + Send + Sync + 'static,
+    {
+        Self {
+            title: title.to_dyn(),
+            image: None,
+            groups: Vec::new(),
+            more: Vec::new(),
+            more_label: String::from("More").to_dyn(),
+        }
+    }
+
+    /// Sets the image shown below the title.
+    pub fn image(mut self, image: Image) -> Self {
+        self.image = Some(image);
+        self
+    }
+
+    /// Appends a group of rows always shown.
+    pub fn group(mut self, group: InfoboxGroup) -> Self {
+        self.groups.push(group);
+        self
+    }
+
+    /// Appends a group of rows to the collapsible "more" part.
+    pub fn more(mut self, group: InfoboxGroup) -> Self {
+        self.more.push(group);
+        self
+    }
+
+    /// Sets the label of the collapsible "more" part, such as in the language
+    /// of the site.
+    pub fn more_label<T>(mut self, label: T) -> Self
+    where
+        T: Component<Kind = InlineComponent> + Send + Sync + 'static,
+    {
+        self.more_label = label.to_dyn();
+        self
+    }
+}
+
+impl Component for Infobox {
+    type Kind = BlockComponent;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        self.to_html_with(fmt, ctx, &Attributes::default(), "div")
+    }
+
+    fn to_html_with(
+        &self,
+        fmt: &mut fmt::Formatter,
+        ctx: Context,
+        attrs: &Attributes,
+        _wrapper: &str,
+    ) -> fmt::Result {
+        ctx.require_style(INFOBOX_STYLE);
+        write!(
+            fmt,
+            "<aside{}><div class=\"infobox-title\">{}</div>",
+            attrs.display(ctx, "infobox"),
+            ctx.renderer(&self.title),
+        )?;
+        if let Some(image) = &self.image {
+            write!(
+                fmt,
+                "<div class=\"infobox-image\">{}</div>",
+                ctx.renderer(image)
+            )?;
+        }
+        for group in &self.groups {
+            write!(fmt, "{}", ctx.renderer(group))?;
+        }
+        if !self.more.is_empty() {
+            write!(
+                fmt,
+                "<details class=\"infobox-more\"><summary>{}</summary>",
+                ctx.renderer(&self.more_label)
+            )?;
+            for group in &self.more {
+                write!(fmt, "{}", ctx.renderer(group))?;
+            }
+            write!(fmt, "</details>")?;
+        }
+        write!(fmt, "</aside>")?;
+        Ok(())
+    }
+}
+
+/// A group of rows of an [`Infobox`], possibly with a heading.
+#[derive(Debug, Clone, Default)]
+pub struct InfoboxGroup {
+    /// Heading of the group.
+    pub heading: Option<DynComponent<InlineComponent>>,
+    /// Rows of the group, in order.
+    pub rows: Vec<InfoboxRow>,
+}
+
+impl InfoboxGroup {
+    /// Creates an empty group without heading.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty group with the given heading.
+    pub fn with_heading<T>(heading: T) -> Self
+    where
+        T: Component<Kind = InlineComponent> + Send + Sync + 'static,
+    {
+        Self { heading: Some(heading.to_dyn()), rows: Vec::new() }
+    }
+
+    /// Appends a row with the given label and value.
+    pub fn row<L, V>(mut self, label: L, value: V) -> Self
+    where
+        L: Component<Kind = InlineComponent> + Send + Sync + 'static,
+        V: Component<Kind = InlineComponent> + Send + Sync + 'static,
+    {
+        self.rows
+            .push(InfoboxRow { label: label.to_dyn(), value: value.to_dyn() });
+        self
+    }
+}
+
+impl Component for InfoboxGroup {
+    type Kind = BlockComponent;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        write!(fmt, "<div class=\"infobox-group\">")?;
+        if let Some(heading) = &self.heading {
+            write!(
+                fmt,
+                "<div class=\"infobox-heading\">{}</div>",
+                ctx.renderer(heading)
+            )?;
+        }
+        write!(fmt, "<dl class=\"infobox-rows\">")?;
+        for row in &self.rows {
+            write!(fmt, "{}", ctx.renderer(row))?;
+        }
+        write!(fmt, "</dl></div>")?;
+        Ok(())
+    }
+}
+
+/// A label/value row of an [`InfoboxGroup`].
+#[derive(Debug, Clone)]
+pub struct InfoboxRow {
+    /// The label, such as "Native to".
+    pub label: DynComponent<InlineComponent>,
+    /// The value, such as "Portugal".
+    pub value: DynComponent<InlineComponent>,
+}
+
+impl Component for InfoboxRow {
+    type Kind = BlockComponent;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        write!(
+            fmt,
+            "<dt class=\"infobox-label\">{}</dt><dd \
+             class=\"infobox-value\">{}</dd>",
+            ctx.renderer(&self.label),
+            ctx.renderer(&self.value),
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Infobox, InfoboxGroup};
+    use crate::component::TestContext;
+
+    #[test]
+    fn more_is_collapsible() {
+        let test = TestContext::new("langs/div-prt");
+        let ctx = test.ctx();
+        let infobox = Infobox::new("Divine Portuguese")
+            .group(InfoboxGroup::new().row("Native to", "Portugal"))
+            .more(InfoboxGroup::with_heading("Codes").row("ISO", "none"));
+        assert_eq!(
+            ctx.renderer(&infobox).to_string(),
+            "<aside class=\"infobox\"><div class=\"infobox-title\">Divine \
+             Portuguese</div><div class=\"infobox-group\"><dl \
+             class=\"infobox-rows\"><dt class=\"infobox-label\">Native \
+             to</dt><dd \
+             class=\"infobox-value\">Portugal</dd></dl></div><details \
+             class=\"infobox-more\"><summary>More</summary><div \
+             class=\"infobox-group\"><div \
+             class=\"infobox-heading\">Codes</div><dl \
+             class=\"infobox-rows\"><dt class=\"infobox-label\">ISO</dt><dd \
+             class=\"infobox-value\">none</dd></dl></div></details></aside>"
+        );
+
+        assert!(test.state.styles()[0].starts_with(".infobox { float: right;"));
+
+        let infobox = infobox.more_label("Mais");
+        assert!(ctx
+            .renderer(&infobox)
+            .to_string()
+            .contains("<summary>Mais</summary>"));
+    }
+}
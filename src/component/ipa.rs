@@ -0,0 +1,593 @@
+//! This module exports the phoneme charts of the International Phonetic
+//! Alphabet: a grid of consonants by place and manner of articulation, and a
+//! trapezoid of vowels by height and backness.
+
+use crate::{
+    component::{BlockComponent, Component, Context},
+    location::Location,
+};
+use std::fmt;
+
+/// The place of articulation of a consonant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Place {
+    /// Both lips, as in [p].
+    Bilabial,
+    /// Lower lip and upper teeth, as in [f].
+    Labiodental,
+    /// Tongue and upper teeth, as in [θ].
+    Dental,
+    /// Tongue and alveolar ridge, as in [t].
+    Alveolar,
+    /// Tongue behind the alveolar ridge, as in [ʃ].
+    Postalveolar,
+    /// Tongue tip curled back, as in [ʈ].
+    Retroflex,
+    /// Tongue and hard palate, as in [j].
+    Palatal,
+    /// Tongue and soft palate, as in [k].
+    Velar,
+    /// Tongue and uvula, as in [q].
+    Uvular,
+    /// Root of the tongue and pharynx, as in [ħ].
+    Pharyngeal,
+    /// Vocal folds, as in [h].
+    Glottal,
+}
+
+impl Place {
+    /// Every place, in the order of the chart.
+    pub const ALL: [Place; 11] = [
+        Place::Bilabial,
+        Place::Labiodental,
+        Place::Dental,
+        Place::Alveolar,
+        Place::Postalveolar,
+        Place::Retroflex,
+        Place::Palatal,
+        Place::Velar,
+        Place::Uvular,
+        Place::Pharyngeal,
+        Place::Glottal,
+    ];
+
+    /// The name of the place in the chart.
+    pub fn label(self) -> &'static str {
+        match self {
+            Place::Bilabial => "Bilabial",
+            Place::Labiodental => "Labiodental",
+            Place::Dental => "Dental",
+            Place::Alveolar => "Alveolar",
+            Place::Postalveolar => "Postalveolar",
+            Place::Retroflex => "Retroflex",
+            Place::Palatal => "Palatal",
+            Place::Velar => "Velar",
+            Place::Uvular => "Uvular",
+            Place::Pharyngeal => "Pharyngeal",
+            Place::Glottal => "Glottal",
+        }
+    }
+}
+
+/// The manner of articulation of a consonant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Manner {
+    /// Airflow fully stopped, as in [p].
+    Plosive,
+    /// Airflow through the nose, as in [m].
+    Nasal,
+    /// Repeated vibration, as in [r].
+    Trill,
+    /// Single brief contact, as in [ɾ].
+    Tap,
+    /// Turbulent airflow, as in [s].
+    Fricative,
+    /// Turbulent airflow along the sides of the tongue, as in [ɬ].
+    LateralFricative,
+    /// Narrowing without turbulence, as in [ɹ].
+    Approximant,
+    /// Narrowing along the sides of the tongue, as in [l].
+    LateralApproximant,
+}
+
+impl Manner {
+    /// Every manner, in the order of the chart.
+    pub const ALL: [Manner; 8] = [
+        Manner::Plosive,
+        Manner::Nasal,
+        Manner::Trill,
+        Manner::Tap,
+        Manner::Fricative,
+        Manner::LateralFricative,
+        Manner::Approximant,
+        Manner::LateralApproximant,
+    ];
+
+    /// The name of the manner in the chart.
+    pub fn label(self) -> &'static str {
+        match self {
+            Manner::Plosive => "Plosive",
+            Manner::Nasal => "Nasal",
+            Manner::Trill => "Trill",
+            Manner::Tap => "Tap or flap",
+            Manner::Fricative => "Fricative",
+            Manner::LateralFricative => "Lateral fricative",
+            Manner::Approximant => "Approximant",
+            Manner::LateralApproximant => "Lateral approximant",
+        }
+    }
+}
+
+/// Whether the vocal folds vibrate while a consonant is articulated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Voicing {
+    /// Without vibration, as in [p].
+    Voiceless,
+    /// With vibration, as in [b].
+    Voiced,
+}
+
+/// Tests whether the articulation is judged impossible by the IPA chart, which
+/// shades its cell.
+fn is_impossible(place: Place, manner: Manner, voicing: Voicing) -> bool {
+    use self::{Manner::*, Place::*};
+
+    match (manner, place) {
+        (Nasal, Pharyngeal | Glottal) => true,
+        (Trill | Tap, Velar | Glottal) => true,
+        (
+            LateralFricative | LateralApproximant,
+            Bilabial | Labiodental | Pharyngeal | Glottal,
+        ) => true,
+        (Plosive, Pharyngeal) => true,
+        (Plosive, Glottal) => voicing == Voicing::Voiced,
+        _ => false,
+    }
+}
+
+/// A consonant phoneme.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Consonant {
+    /// The IPA symbol of the phoneme.
+    pub symbol: String,
+    /// The place of articulation.
+    pub place: Place,
+    /// The manner of articulation.
+    pub manner: Manner,
+    /// Whether the phoneme is voiced.
+    pub voicing: Voicing,
+    /// Where the symbol links to, such as the section about the phoneme or a
+    /// recording of it.
+    pub link: Option<Location>,
+}
+
+impl Consonant {
+    /// Creates a consonant without link.
+    pub fn new<S>(
+        symbol: S,
+        place: Place,
+        manner: Manner,
+        voicing: Voicing,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        Self { symbol: symbol.into(), place, manner, voicing, link: None }
+    }
+
+    /// Links the symbol to the given location.
+    pub fn link(mut self, location: Location) -> Self {
+        self.link = Some(location);
+        self
+    }
+}
+
+/// The chart of the consonants of a language. Only the places and manners
+/// used by the consonants are shown, unless the full chart is asked for. Cells
+/// of impossible articulations are marked as such.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ConsonantChart {
+    /// The title of the chart.
+    pub title: String,
+    /// The consonants in the chart.
+    pub consonants: Vec<Consonant>,
+    /// Whether every place and manner is shown.
+    pub full: bool,
+}
+
+impl ConsonantChart {
+    /// Creates a chart of the given consonants.
+    pub fn new<S>(title: S, consonants: Vec<Consonant>) -> Self
+    where
+        S: Into<String>,
+    {
+        Self { title: title.into(), consonants, full: false }
+    }
+
+    /// Shows every place and manner, even if not used.
+    pub fn full(mut self) -> Self {
+        self.full = true;
+        self
+    }
+}
+
+impl Component for ConsonantChart {
+    type Kind = BlockComponent;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        let places = Place::ALL
+            .iter()
+            .copied()
+            .filter(|&place| {
+                self.full || self.consonants.iter().any(|c| c.place == place)
+            })
+            .collect::<Vec<_>>();
+        let manners = Manner::ALL
+            .iter()
+            .copied()
+            .filter(|&manner| {
+                self.full || self.consonants.iter().any(|c| c.manner == manner)
+            })
+            .collect::<Vec<_>>();
+
+        write!(
+            fmt,
+            "<div class=\"table-wrapper\"><table class=\"ipa-chart \
+             consonant-chart\"><caption \
+             class=\"table-title\">{}</caption><thead><tr><td></td>",
+            ctx.renderer(&self.title)
+        )?;
+        for place in &places {
+            write!(
+                fmt,
+                "<th scope=\"colgroup\" colspan=\"2\">{}</th>",
+                place.label()
+            )?;
+        }
+        write!(fmt, "</tr></thead><tbody>")?;
+
+        for &manner in &manners {
+            write!(fmt, "<tr><th scope=\"row\">{}</th>", manner.label())?;
+            for &place in &places {
+                for voicing in [Voicing::Voiceless, Voicing::Voiced] {
+                    if is_impossible(place, manner, voicing) {
+                        write!(fmt, "<td class=\"ipa-impossible\"></td>")?;
+                        continue;
+                    }
+                    write!(fmt, "<td>")?;
+                    let cell = self.consonants.iter().filter(|consonant| {
+                        consonant.place == place
+                            && consonant.manner == manner
+                            && consonant.voicing == voicing
+                    });
+                    for (i, consonant) in cell.enumerate() {
+                        if i > 0 {
+                            write!(fmt, " ")?;
+                        }
+                        write_symbol(
+                            fmt,
+                            ctx,
+                            &consonant.symbol,
+                            consonant.link.as_ref(),
+                            false,
+                        )?;
+                    }
+                    write!(fmt, "</td>")?;
+                }
+            }
+            write!(fmt, "</tr>")?;
+        }
+
+        write!(fmt, "</tbody></table></div>")?;
+        Ok(())
+    }
+}
+
+/// The height of the tongue while a vowel is articulated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Height {
+    /// As in [i].
+    Close,
+    /// As in [ɪ].
+    NearClose,
+    /// As in [e].
+    CloseMid,
+    /// As in [ə].
+    Mid,
+    /// As in [ɛ].
+    OpenMid,
+    /// As in [æ].
+    NearOpen,
+    /// As in [a].
+    Open,
+}
+
+/// How far back the tongue is while a vowel is articulated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Backness {
+    /// As in [i].
+    Front,
+    /// As in [ɪ].
+    NearFront,
+    /// As in [ɨ].
+    Central,
+    /// As in [ʊ].
+    NearBack,
+    /// As in [u].
+    Back,
+}
+
+/// Whether the lips are rounded while a vowel is articulated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Rounding {
+    /// As in [i].
+    Unrounded,
+    /// As in [y].
+    Rounded,
+}
+
+/// A vowel phoneme.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vowel {
+    /// The IPA symbol of the phoneme.
+    pub symbol: String,
+    /// The height of the tongue.
+    pub height: Height,
+    /// The backness of the tongue.
+    pub backness: Backness,
+    /// Whether the lips are rounded.
+    pub rounding: Rounding,
+    /// Where the symbol links to, such as the section about the phoneme or a
+    /// recording of it.
+    pub link: Option<Location>,
+}
+
+impl Vowel {
+    /// Creates a vowel without link.
+    pub fn new<S>(
+        symbol: S,
+        height: Height,
+        backness: Backness,
+        rounding: Rounding,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        Self { symbol: symbol.into(), height, backness, rounding, link: None }
+    }
+
+    /// Links the symbol to the given location.
+    pub fn link(mut self, location: Location) -> Self {
+        self.link = Some(location);
+        self
+    }
+}
+
+/// The vowel trapezoid of a language, drawn as an inline SVG. Unrounded vowels
+/// are placed at the left of their point, rounded ones at the right.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct VowelChart {
+    /// The title of the chart.
+    pub title: String,
+    /// The vowels in the chart.
+    pub vowels: Vec<Vowel>,
+}
+
+impl VowelChart {
+    /// Creates a chart of the given vowels.
+    pub fn new<S>(title: S, vowels: Vec<Vowel>) -> Self
+    where
+        S: Into<String>,
+    {
+        Self { title: title.into(), vowels }
+    }
+}
+
+/// Width of the top of the trapezoid.
+const TRAPEZOID_WIDTH: i32 = 300;
+/// Height of the trapezoid.
+const TRAPEZOID_HEIGHT: i32 = 240;
+
+/// The point of a vowel in the trapezoid.
+fn vowel_point(height: Height, backness: Backness) -> (i32, i32) {
+    let y = height as i32 * TRAPEZOID_HEIGHT / 6;
+    let front = height as i32 * TRAPEZOID_WIDTH / 12;
+    let x = front + (TRAPEZOID_WIDTH - front) * backness as i32 / 4;
+    (x, y)
+}
+
+impl Component for VowelChart {
+    type Kind = BlockComponent;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        let (w, h) = (TRAPEZOID_WIDTH, TRAPEZOID_HEIGHT);
+        write!(
+            fmt,
+            "<figure class=\"ipa-chart vowel-chart\"><svg viewBox=\"-40 -20 \
+             {} {}\" role=\"img\"><title>{}</title>",
+            w + 80,
+            h + 40,
+            ctx.renderer(&self.title),
+        )?;
+        let (close_mid, open_mid) = (h / 3, h * 2 / 3);
+        write!(
+            fmt,
+            "<g fill=\"none\" stroke=\"currentColor\"><polygon points=\"0,0 \
+             {w},0 {w},{h} {bottom},{h}\"/><line x1=\"{cm}\" \
+             y1=\"{close_mid}\" x2=\"{w}\" y2=\"{close_mid}\"/><line \
+             x1=\"{om}\" y1=\"{open_mid}\" x2=\"{w}\" \
+             y2=\"{open_mid}\"/><line x1=\"{top_center}\" y1=\"0\" \
+             x2=\"{bottom_center}\" y2=\"{h}\"/></g>",
+            w = w,
+            h = h,
+            bottom = w / 2,
+            cm = w / 6,
+            om = w / 3,
+            close_mid = close_mid,
+            open_mid = open_mid,
+            top_center = w / 2,
+            bottom_center = w * 3 / 4,
+        )?;
+
+        let mut points = Vec::new();
+        for vowel in &self.vowels {
+            let point = vowel_point(vowel.height, vowel.backness);
+            if !points.contains(&point) {
+                points.push(point);
+                write!(
+                    fmt,
+                    "<circle cx=\"{}\" cy=\"{}\" r=\"3\" \
+                     fill=\"currentColor\"/>",
+                    point.0, point.1
+                )?;
+            }
+        }
+        for vowel in &self.vowels {
+            let (x, y) = vowel_point(vowel.height, vowel.backness);
+            let (x, anchor) = match vowel.rounding {
+                Rounding::Unrounded => (x - 8, "end"),
+                Rounding::Rounded => (x + 8, "start"),
+            };
+            write!(
+                fmt,
+                "<text x=\"{}\" y=\"{}\" text-anchor=\"{}\" \
+                 dominant-baseline=\"middle\">",
+                x, y, anchor
+            )?;
+            write_symbol(fmt, ctx, &vowel.symbol, vowel.link.as_ref(), true)?;
+            write!(fmt, "</text>")?;
+        }
+
+        write!(
+            fmt,
+            "</svg><figcaption class=\"table-title\">{}</figcaption></figure>",
+            ctx.renderer(&self.title)
+        )?;
+        Ok(())
+    }
+}
+
+/// Writes the symbol of a phoneme, linking it if a location is given. Inside
+/// SVG text, an unlinked symbol is a `<tspan>`, since HTML elements cannot be
+/// nested in SVG.
+fn write_symbol(
+    fmt: &mut fmt::Formatter,
+    ctx: Context,
+    symbol: &str,
+    link: Option<&Location>,
+    in_svg: bool,
+) -> fmt::Result {
+    match link {
+        Some(location) => write!(
+            fmt,
+            "<a href=\"{}\" class=\"ipa\">{}</a>",
+            ctx.renderer(location),
+            ctx.renderer(symbol)
+        ),
+        None => {
+            let tag = if in_svg { "tspan" } else { "span" };
+            write!(
+                fmt,
+                "<{0} class=\"ipa\">{1}</{0}>",
+                tag,
+                ctx.renderer(symbol)
+            )
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        is_impossible,
+        Backness,
+        Consonant,
+        ConsonantChart,
+        Height,
+        Manner,
+        Place,
+        Rounding,
+        Voicing,
+        Vowel,
+        VowelChart,
+    };
+    use crate::{
+        component::TestContext,
+        location::{InternalLoc, Location},
+    };
+
+    #[test]
+    fn consonant_grid() {
+        let test = TestContext::new("langs/lat/index.html");
+        let ctx = test.ctx();
+        let chart = ConsonantChart::new(
+            "Consonants",
+            vec![
+                Consonant::new(
+                    "p",
+                    Place::Bilabial,
+                    Manner::Plosive,
+                    Voicing::Voiceless,
+                ),
+                Consonant::new(
+                    "h",
+                    Place::Glottal,
+                    Manner::Fricative,
+                    Voicing::Voiceless,
+                )
+                .link(Location::Internal(
+                    InternalLoc::parse("langs/lat#h").unwrap(),
+                )),
+            ],
+        );
+        assert_eq!(
+            ctx.renderer(&chart).to_string(),
+            "<div class=\"table-wrapper\"><table class=\"ipa-chart \
+             consonant-chart\"><caption \
+             class=\"table-title\">Consonants</caption><thead><tr><td></\
+             td><th scope=\"colgroup\" colspan=\"2\">Bilabial</th><th \
+             scope=\"colgroup\" \
+             colspan=\"2\">Glottal</th></tr></thead><tbody><tr><th \
+             scope=\"row\">Plosive</th><td><span \
+             class=\"ipa\">p</span></td><td></td><td></td><td \
+             class=\"ipa-impossible\"></td></tr><tr><th \
+             scope=\"row\">Fricative</th><td></td><td></td><td><a href=\"#h\" \
+             class=\"ipa\">h</a></td><td></td></tr></tbody></table></div>"
+        );
+
+        let chart = VowelChart::new(
+            "Vowels",
+            vec![
+                Vowel::new(
+                    "i",
+                    Height::Close,
+                    Backness::Front,
+                    Rounding::Unrounded,
+                ),
+                Vowel::new(
+                    "a",
+                    Height::Open,
+                    Backness::Central,
+                    Rounding::Unrounded,
+                ),
+            ],
+        );
+        let html = ctx.renderer(&chart).to_string();
+        assert!(html.contains(
+            "<text x=\"-8\" y=\"0\" text-anchor=\"end\" \
+             dominant-baseline=\"middle\"><tspan \
+             class=\"ipa\">i</tspan></text>"
+        ));
+        assert!(html.contains("<text x=\"217\" y=\"240\" text-anchor=\"end\""));
+    }
+
+    #[test]
+    fn shaded_plosive_cells() {
+        use super::{Manner::Plosive, Place::*, Voicing::*};
+
+        assert!(is_impossible(Pharyngeal, Plosive, Voiceless));
+        assert!(is_impossible(Pharyngeal, Plosive, Voiced));
+        assert!(!is_impossible(Glottal, Plosive, Voiceless));
+        assert!(is_impossible(Glottal, Plosive, Voiced));
+    }
+}
@@ -0,0 +1,308 @@
+//! This module exports components marking text as being in another language
+//! or script, including constructed scripts displayed with a font of the
+//! assets.
+
+use crate::{
+    component::{BlockComponent, Component, Context, InlineComponent},
+    location::{Id, InternalPath},
+};
+use anyhow::anyhow;
+use std::fmt;
+
+/// The direction of the text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    /// Left to right.
+    Ltr,
+    /// Right to left.
+    Rtl,
+    /// Guessed by the browser from the text.
+    Auto,
+}
+
+impl Direction {
+    /// The value of the `dir` attribute.
+    pub fn as_attr(self) -> &'static str {
+        match self {
+            Direction::Ltr => "ltr",
+            Direction::Rtl => "rtl",
+            Direction::Auto => "auto",
+        }
+    }
+}
+
+/// How the romanization of a text is displayed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum RomanizationDisplay {
+    /// As a tooltip, when hovering the text.
+    #[default]
+    Tooltip,
+    /// As ruby text above the text. Only inline text can have ruby text,
+    /// blocks fall back to a tooltip.
+    Ruby,
+}
+
+/// A script, usually a constructed one, displayed with a font file of the
+/// assets.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Script {
+    /// The font family name of the script.
+    pub name: Id,
+    /// Path to the font file, in the assets.
+    pub font: InternalPath,
+    /// The direction the script is written in, if not the language's default.
+    pub dir: Option<Direction>,
+    /// Latin transcriptions of letters of the script, used to romanize texts.
+    pub letters: Vec<(String, String)>,
+}
+
+impl Script {
+    /// Creates a script with the given font and no romanization.
+    pub fn new(name: Id, font: InternalPath) -> Self {
+        Self { name, font, dir: None, letters: Vec::new() }
+    }
+
+    /// Sets the direction the script is written in.
+    pub fn dir(mut self, dir: Direction) -> Self {
+        self.dir = Some(dir);
+        self
+    }
+
+    /// Adds the Latin transcription of a letter, which may have more than one
+    /// character.
+    pub fn letter<L, R>(mut self, letter: L, latin: R) -> Self
+    where
+        L: Into<String>,
+        R: Into<String>,
+    {
+        self.letters.push((letter.into(), latin.into()));
+        self
+    }
+
+    /// Romanizes a text, letter by letter, the longest letter first. Other
+    /// characters are kept as they are.
+    pub fn romanize(&self, text: &str) -> String {
+        let mut romanized = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(ch) = rest.chars().next() {
+            let letter = self
+                .letters
+                .iter()
+                .filter(|(letter, _)| {
+                    !letter.is_empty() && rest.starts_with(letter.as_str())
+                })
+                .max_by_key(|(letter, _)| letter.len());
+            match letter {
+                Some((letter, latin)) => {
+                    romanized.push_str(latin);
+                    rest = &rest[letter.len() ..];
+                },
+                None => {
+                    romanized.push(ch);
+                    rest = &rest[ch.len_utf8() ..];
+                },
+            }
+        }
+        romanized
+    }
+}
+
+/// Marks the wrapped component as being in the given language, such as `ar`
+/// or `art-x-div`, optionally in a script with its own font.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Lang<T, K>
+where
+    T: Component<Kind = K>,
+{
+    /// The wrapped component.
+    pub content: T,
+    /// The language tag.
+    pub tag: String,
+    /// The direction of the text, if not inherited.
+    pub dir: Option<Direction>,
+    /// The script of the text, if displayed with a font of the assets.
+    pub script: Option<Script>,
+    /// The romanization of the text.
+    pub romanization: Option<String>,
+    /// How the romanization is displayed.
+    pub romanization_display: RomanizationDisplay,
+}
+
+impl<T, K> Lang<T, K>
+where
+    T: Component<Kind = K>,
+{
+    /// Marks the given component as being in the given language.
+    pub fn new<S>(tag: S, content: T) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            content,
+            tag: tag.into(),
+            dir: None,
+            script: None,
+            romanization: None,
+            romanization_display: RomanizationDisplay::default(),
+        }
+    }
+
+    /// Sets the direction of the text.
+    pub fn dir(mut self, dir: Direction) -> Self {
+        self.dir = Some(dir);
+        self
+    }
+
+    /// Displays the text in the given script.
+    pub fn script(mut self, script: Script) -> Self {
+        self.script = Some(script);
+        self
+    }
+
+    /// Sets the romanization of the text.
+    pub fn romanization<S>(mut self, romanization: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.romanization = Some(romanization.into());
+        self
+    }
+
+    /// Displays the romanization as ruby text.
+    pub fn ruby(mut self) -> Self {
+        self.romanization_display = RomanizationDisplay::Ruby;
+        self
+    }
+
+    /// Writes the attributes shared by inline and block text. Requires the
+    /// font of the script.
+    fn write_attrs(
+        &self,
+        fmt: &mut fmt::Formatter,
+        ctx: Context,
+    ) -> fmt::Result {
+        write!(fmt, " lang=\"{}\"", ctx.renderer(&self.tag))?;
+        let dir = self
+            .dir
+            .or_else(|| self.script.as_ref().and_then(|script| script.dir));
+        if let Some(dir) = dir {
+            write!(fmt, " dir=\"{}\"", dir.as_attr())?;
+        }
+        write!(fmt, " class=\"lang\"")?;
+        if let Some(script) = &self.script {
+            if let Some(assets) = ctx.assets() {
+                if !assets.fs_path(&script.font).is_file() {
+                    Err(ctx.report(anyhow!(
+                        "Missing font file {}",
+                        assets.fs_path(&script.font).display()
+                    )))?;
+                }
+            }
+            ctx.require_font(&script.name, &script.font);
+            write!(fmt, " style=\"font-family: '{}'\"", script.name)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T> Lang<T, InlineComponent>
+where
+    T: Component<Kind = InlineComponent> + AsRef<str>,
+{
+    /// Marks the given text as being in the given language and script, and
+    /// romanizes it with the letters of the script.
+    pub fn romanized<S>(tag: S, script: Script, text: T) -> Self
+    where
+        S: Into<String>,
+    {
+        let romanization = script.romanize(text.as_ref());
+        Self::new(tag, text).script(script).romanization(romanization)
+    }
+}
+
+impl<T> Component for Lang<T, InlineComponent>
+where
+    T: Component<Kind = InlineComponent>,
+{
+    type Kind = InlineComponent;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        let ruby = match (&self.romanization, self.romanization_display) {
+            (Some(romanization), RomanizationDisplay::Ruby) => {
+                Some(romanization)
+            },
+            _ => None,
+        };
+        if ruby.is_some() {
+            write!(fmt, "<ruby class=\"romanized\">")?;
+        }
+        write!(fmt, "<span")?;
+        self.write_attrs(fmt, ctx)?;
+        if let (Some(romanization), None) = (&self.romanization, ruby) {
+            write!(fmt, " title=\"{}\"", ctx.renderer(romanization))?;
+        }
+        write!(fmt, ">{}</span>", ctx.renderer(&self.content))?;
+        if let Some(romanization) = ruby {
+            write!(
+                fmt,
+                "<rp>(</rp><rt>{}</rt><rp>)</rp></ruby>",
+                ctx.renderer(romanization)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl<T> Component for Lang<T, BlockComponent>
+where
+    T: Component<Kind = BlockComponent>,
+{
+    type Kind = BlockComponent;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        write!(fmt, "<div")?;
+        self.write_attrs(fmt, ctx)?;
+        if let Some(romanization) = &self.romanization {
+            write!(fmt, " title=\"{}\"", ctx.renderer(romanization))?;
+        }
+        write!(fmt, ">{}</div>", ctx.renderer(&self.content))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Direction, Lang, Script};
+    use crate::{
+        component::TestContext,
+        location::{Id, InternalPath},
+    };
+
+    #[test]
+    fn romanized_ruby() {
+        let test = TestContext::new("langs/div/index.html");
+        let ctx = test.ctx();
+        let script = Script::new(
+            Id::new("divine").unwrap(),
+            InternalPath::parse("fonts/divine.woff2").unwrap(),
+        )
+        .dir(Direction::Rtl)
+        .letter("\u{e001}\u{e000}", "ka")
+        .letter("\u{e001}", "k")
+        .letter("\u{e000}", "a");
+        let text =
+            Lang::romanized("art-x-div", script, "\u{e001}\u{e000}\u{e000}")
+                .ruby();
+        assert_eq!(
+            ctx.renderer(&text).to_string(),
+            "<ruby class=\"romanized\"><span lang=\"art-x-div\" dir=\"rtl\" \
+             class=\"lang\" style=\"font-family: \
+             'divine'\">\u{e001}\u{e000}\u{e000}</span><rp>(</rp><rt>kaa</\
+             rt><rp>)</rp></ruby>"
+        );
+        assert_eq!(
+            test.state.fonts().get(&Id::new("divine").unwrap()),
+            Some(&InternalPath::parse("fonts/divine.woff2").unwrap())
+        );
+    }
+}
@@ -1,6 +1,12 @@
 //! This module exports list components.
 
-use crate::component::{BlockComponent, Component, Context};
+use crate::component::{
+    style::Attributes,
+    BlockComponent,
+    Component,
+    Context,
+    InlineComponent,
+};
 use std::fmt;
 
 /// A list of elements, with enumeration on each item.
@@ -9,6 +15,39 @@ pub struct OrderedList<T>(pub Vec<T>)
 where
     T: Component;
 
+impl<T> OrderedList<T>
+where
+    T: Component,
+{
+    /// Sets the number of the first item.
+    pub fn start(self, start: i64) -> OrderedListOptions<T> {
+        OrderedListOptions::new(self).start(start)
+    }
+
+    /// Numbers the items in decreasing order.
+    pub fn reversed(self) -> OrderedListOptions<T> {
+        OrderedListOptions::new(self).reversed()
+    }
+
+    /// Sets the kind of numbering of the items.
+    pub fn numbering(self, numbering: Numbering) -> OrderedListOptions<T> {
+        OrderedListOptions::new(self).numbering(numbering)
+    }
+
+    /// Writes the items and the end of the list, after its opening tag.
+    fn write_items(
+        &self,
+        fmt: &mut fmt::Formatter,
+        ctx: Context,
+    ) -> fmt::Result {
+        for item in &self.0 {
+            write!(fmt, "<li>{}</li>", ctx.renderer(item))?;
+        }
+        write!(fmt, "</ol>")?;
+        Ok(())
+    }
+}
+
 impl<T> Default for OrderedList<T>
 where
     T: Component,
@@ -25,13 +64,130 @@ where
     type Kind = BlockComponent;
 
     fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
-        write!(fmt, "<ol class=\"ordered-list\">")?;
-        for item in &self.0 {
-            write!(fmt, "<li>{}</li>", ctx.renderer(item))?;
+        self.to_html_with(fmt, ctx, &Attributes::default(), "div")
+    }
+
+    fn to_html_with(
+        &self,
+        fmt: &mut fmt::Formatter,
+        ctx: Context,
+        attrs: &Attributes,
+        _wrapper: &str,
+    ) -> fmt::Result {
+        write!(fmt, "<ol{}>", attrs.display(ctx, "ordered-list"))?;
+        self.write_items(fmt, ctx)
+    }
+}
+
+/// An [`OrderedList`] with options on how its items are numbered, created by
+/// the setters of the list.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OrderedListOptions<T>
+where
+    T: Component,
+{
+    /// The list displayed.
+    pub list: OrderedList<T>,
+    /// The number of the first item, 1 if `None`.
+    pub start: Option<i64>,
+    /// Whether the items are numbered in decreasing order.
+    pub reversed: bool,
+    /// The kind of numbering of the items.
+    pub numbering: Numbering,
+}
+
+impl<T> OrderedListOptions<T>
+where
+    T: Component,
+{
+    /// Displays the given list with decimal numbering starting at 1.
+    pub fn new(list: OrderedList<T>) -> Self {
+        Self {
+            list,
+            start: None,
+            reversed: false,
+            numbering: Numbering::default(),
         }
-        write!(fmt, "</ol>")?;
+    }
 
-        Ok(())
+    /// Sets the number of the first item.
+    pub fn start(mut self, start: i64) -> Self {
+        self.start = Some(start);
+        self
+    }
+
+    /// Numbers the items in decreasing order.
+    pub fn reversed(mut self) -> Self {
+        self.reversed = true;
+        self
+    }
+
+    /// Sets the kind of numbering of the items.
+    pub fn numbering(mut self, numbering: Numbering) -> Self {
+        self.numbering = numbering;
+        self
+    }
+}
+
+impl<T> Component for OrderedListOptions<T>
+where
+    T: Component,
+{
+    type Kind = BlockComponent;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        self.to_html_with(fmt, ctx, &Attributes::default(), "div")
+    }
+
+    fn to_html_with(
+        &self,
+        fmt: &mut fmt::Formatter,
+        ctx: Context,
+        attrs: &Attributes,
+        _wrapper: &str,
+    ) -> fmt::Result {
+        write!(fmt, "<ol{}", attrs.display(ctx, "ordered-list"))?;
+        if let Some(start) = self.start {
+            write!(fmt, " start=\"{}\"", start)?;
+        }
+        if self.reversed {
+            write!(fmt, " reversed")?;
+        }
+        if let Some(kind) = self.numbering.as_type() {
+            write!(fmt, " type=\"{}\"", kind)?;
+        }
+        write!(fmt, ">")?;
+        self.list.write_items(fmt, ctx)
+    }
+}
+
+/// The kind of numbering of an [`OrderedList`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum Numbering {
+    /// 1, 2, 3...
+    #[default]
+    Decimal,
+    /// a, b, c...
+    LowerAlpha,
+    /// A, B, C...
+    UpperAlpha,
+    /// i, ii, iii...
+    LowerRoman,
+    /// I, II, III...
+    UpperRoman,
+}
+
+impl Numbering {
+    /// The value of the `type` attribute of the list, `None` for the default
+    /// numbering.
+    pub fn as_type(self) -> Option<&'static str> {
+        match self {
+            Numbering::Decimal => None,
+            Numbering::LowerAlpha => Some("a"),
+            Numbering::UpperAlpha => Some("A"),
+            Numbering::LowerRoman => Some("i"),
+            Numbering::UpperRoman => Some("I"),
+        }
     }
 }
 
@@ -58,7 +214,17 @@ where
     type Kind = BlockComponent;
 
     fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
-        write!(fmt, "<ul class=\"unordered-list\">")?;
+        self.to_html_with(fmt, ctx, &Attributes::default(), "div")
+    }
+
+    fn to_html_with(
+        &self,
+        fmt: &mut fmt::Formatter,
+        ctx: Context,
+        attrs: &Attributes,
+        _wrapper: &str,
+    ) -> fmt::Result {
+        write!(fmt, "<ul{}>", attrs.display(ctx, "unordered-list"))?;
         for item in &self.0 {
             write!(fmt, "<li>{}</li>", ctx.renderer(item))?;
         }
@@ -90,7 +256,17 @@ where
     type Kind = BlockComponent;
 
     fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
-        write!(fmt, "<ul class=\"unmarked-list\">")?;
+        self.to_html_with(fmt, ctx, &Attributes::default(), "div")
+    }
+
+    fn to_html_with(
+        &self,
+        fmt: &mut fmt::Formatter,
+        ctx: Context,
+        attrs: &Attributes,
+        _wrapper: &str,
+    ) -> fmt::Result {
+        write!(fmt, "<ul{}>", attrs.display(ctx, "unmarked-list"))?;
         for item in &self.0 {
             write!(fmt, "<li>{}</li>", ctx.renderer(item))?;
         }
@@ -99,3 +275,138 @@ where
         Ok(())
     }
 }
+
+/// An item of a list with its own text, followed by a nested list.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ListItem<T, L>
+where
+    T: Component<Kind = InlineComponent>,
+    L: Component<Kind = BlockComponent>,
+{
+    /// The text of the item.
+    pub text: T,
+    /// The list nested under the item.
+    pub sublist: L,
+}
+
+impl<T, L> Component for ListItem<T, L>
+where
+    T: Component<Kind = InlineComponent>,
+    L: Component<Kind = BlockComponent>,
+{
+    type Kind = BlockComponent;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        write!(
+            fmt,
+            "{}{}",
+            ctx.renderer(&self.text),
+            ctx.renderer(&self.sublist)
+        )
+    }
+}
+
+/// A list of terms, each one with its definitions.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DefinitionList<T, D>(pub Vec<Definition<T, D>>)
+where
+    T: Component<Kind = InlineComponent>,
+    D: Component;
+
+impl<T, D> Default for DefinitionList<T, D>
+where
+    T: Component<Kind = InlineComponent>,
+    D: Component,
+{
+    fn default() -> Self {
+        Self(Vec::default())
+    }
+}
+
+impl<T, D> Component for DefinitionList<T, D>
+where
+    T: Component<Kind = InlineComponent>,
+    D: Component,
+{
+    type Kind = BlockComponent;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        self.to_html_with(fmt, ctx, &Attributes::default(), "div")
+    }
+
+    fn to_html_with(
+        &self,
+        fmt: &mut fmt::Formatter,
+        ctx: Context,
+        attrs: &Attributes,
+        _wrapper: &str,
+    ) -> fmt::Result {
+        write!(fmt, "<dl{}>", attrs.display(ctx, "definition-list"))?;
+        for entry in &self.0 {
+            write!(fmt, "<dt>{}</dt>", ctx.renderer(&entry.term))?;
+            for definition in &entry.definitions {
+                write!(fmt, "<dd>{}</dd>", ctx.renderer(definition))?;
+            }
+        }
+        write!(fmt, "</dl>")?;
+
+        Ok(())
+    }
+}
+
+/// A term of a [`DefinitionList`] and its definitions.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Definition<T, D>
+where
+    T: Component<Kind = InlineComponent>,
+    D: Component,
+{
+    /// The term being defined.
+    pub term: T,
+    /// The definitions of the term.
+    pub definitions: Vec<D>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        Definition,
+        DefinitionList,
+        ListItem,
+        Numbering,
+        OrderedList,
+        UnorderedList,
+    };
+    use crate::component::TestContext;
+
+    #[test]
+    fn options_and_nesting() {
+        let test = TestContext::new("rules/index.html");
+        let ctx = test.ctx();
+
+        let list = OrderedList(vec![
+            ListItem { text: "Vowels", sublist: UnorderedList(vec!["a", "e"]) },
+            ListItem { text: "Consonants", sublist: UnorderedList(vec![]) },
+        ])
+        .start(3)
+        .reversed()
+        .numbering(Numbering::UpperRoman);
+        assert_eq!(
+            ctx.renderer(&list).to_string(),
+            "<ol class=\"ordered-list\" start=\"3\" reversed \
+             type=\"I\"><li>Vowels<ul \
+             class=\"unordered-list\"><li>a</li><li>e</li></ul></\
+             li><li>Consonants<ul class=\"unordered-list\"></ul></li></ol>"
+        );
+
+        let list = DefinitionList(vec![Definition {
+            term: "lupus",
+            definitions: vec!["wolf", "wolfish person"],
+        }]);
+        assert_eq!(
+            ctx.renderer(&list).to_string(),
+            "<dl class=\"definition-list\"><dt>lupus</dt><dd>wolf</\
+             dd><dd>wolfish person</dd></dl>"
+        );
+    }
+}
@@ -0,0 +1,130 @@
+//! This module exports math components. Formulas are written in a TeX-like
+//! syntax and converted to MathML, so no script is needed to display them.
+
+mod tex;
+
+pub use self::tex::TexError;
+
+use crate::{
+    component::{
+        numbered::NumberRef,
+        BlockComponent,
+        Component,
+        Context,
+        InlineComponent,
+    },
+    location::{Id, InternalLoc},
+};
+use std::fmt;
+
+/// The counter used to number equations.
+const EQUATION_COUNTER: &str = "equation";
+
+/// A formula in the middle of the text.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Math {
+    tex: Box<str>,
+    mathml: Box<str>,
+}
+
+impl Math {
+    /// Converts the given TeX-like formula into MathML. Supports scripts,
+    /// fractions, roots, matrices, Greek letters and the usual symbols.
+    pub fn new<S>(tex: S) -> anyhow::Result<Self>
+    where
+        S: AsRef<str> + Into<Box<str>>,
+    {
+        let mathml = tex::to_mathml(tex.as_ref())?.into();
+        Ok(Self { tex: tex.into(), mathml })
+    }
+
+    /// The TeX-like source of this formula.
+    pub fn tex(&self) -> &str {
+        &self.tex
+    }
+
+    /// Renders the `<math>` element with the given display mode.
+    fn render(
+        &self,
+        fmt: &mut fmt::Formatter,
+        ctx: Context,
+        display: &str,
+    ) -> fmt::Result {
+        write!(
+            fmt,
+            "<math display=\"{}\" class=\"math\"><semantics>{}<annotation \
+             encoding=\"application/x-tex\">{}</annotation></semantics></math>",
+            display,
+            self.mathml,
+            ctx.renderer(self.tex()),
+        )?;
+        Ok(())
+    }
+}
+
+impl Component for Math {
+    type Kind = InlineComponent;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        self.render(fmt, ctx, "inline")
+    }
+}
+
+/// A formula displayed as a block, optionally numbered.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Equation {
+    /// The formula.
+    pub math: Math,
+    /// ID that references the equation, used by [`EquationRef`].
+    pub id: Option<Id>,
+    /// Whether the equation is numbered.
+    pub numbered: bool,
+}
+
+impl Equation {
+    /// Creates an unnumbered equation without ID.
+    pub fn new(math: Math) -> Self {
+        Self { math, id: None, numbered: false }
+    }
+
+    /// Creates an equation numbered in the page and referenced by the given ID.
+    pub fn numbered(math: Math, id: Id) -> Self {
+        Self { math, id: Some(id), numbered: true }
+    }
+}
+
+impl Component for Equation {
+    type Kind = BlockComponent;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        write!(fmt, "<div class=\"equation\"")?;
+        if let Some(id) = &self.id {
+            write!(fmt, " id=\"{}\"", ctx.renderer(id))?;
+        }
+        write!(fmt, ">")?;
+        self.math.render(fmt, ctx, "block")?;
+        if self.numbered {
+            write!(
+                fmt,
+                "<span class=\"equation-number\">({})</span>",
+                ctx.next_number(EQUATION_COUNTER, self.id.as_ref())
+            )?;
+        }
+        write!(fmt, "</div>")?;
+        Ok(())
+    }
+}
+
+/// A reference to a numbered equation, displayed as its number and linking to
+/// it. The equation may be in another page.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EquationRef(pub InternalLoc);
+
+impl Component for EquationRef {
+    type Kind = InlineComponent;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        let reference = NumberRef::new(EQUATION_COUNTER, self.0.clone());
+        reference.to_html(fmt, ctx)
+    }
+}
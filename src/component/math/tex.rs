@@ -0,0 +1,740 @@
+//! This module converts TeX-like math input into MathML.
+
+use crate::component::html_escape;
+use std::fmt::Write as _;
+use thiserror::Error;
+
+/// Error when converting TeX-like input.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum TexError {
+    /// A command (`\name`) is not supported.
+    #[error("Unknown TeX command \\{0}")]
+    UnknownCommand(String),
+    /// An environment (`\begin{name}`) is not supported.
+    #[error("Unknown TeX environment {0}")]
+    UnknownEnvironment(String),
+    /// A command expected an argument, but there was none.
+    #[error("Missing argument for TeX command \\{0}")]
+    MissingArgument(String),
+    /// A `}`, `\right` or `\end` was expected but not found.
+    #[error("Expected {0} in TeX input")]
+    Expected(&'static str),
+    /// Something was found where it should not be.
+    #[error("Unexpected {0} in TeX input")]
+    Unexpected(&'static str),
+}
+
+/// Converts TeX-like input into MathML elements, without the outer `<math>`
+/// element.
+pub fn to_mathml(tex: &str) -> Result<String, TexError> {
+    let mut parser = Parser { tokens: tokenize(tex), pos: 0 };
+    let row = parser.parse_row()?;
+    match parser.peek() {
+        None => Ok(row),
+        Some(Token::Close) => Err(TexError::Unexpected("}")),
+        Some(Token::Amp) => Err(TexError::Unexpected("&")),
+        Some(Token::RowEnd) => Err(TexError::Unexpected("\\\\")),
+        Some(_) => Err(TexError::Unexpected("\\right or \\end")),
+    }
+}
+
+/// Greek letters and other symbols, as identifiers.
+const IDENTIFIERS: &[(&str, char)] = &[
+    ("alpha", 'α'),
+    ("beta", 'β'),
+    ("gamma", 'γ'),
+    ("delta", 'δ'),
+    ("epsilon", 'ϵ'),
+    ("varepsilon", 'ε'),
+    ("zeta", 'ζ'),
+    ("eta", 'η'),
+    ("theta", 'θ'),
+    ("vartheta", 'ϑ'),
+    ("iota", 'ι'),
+    ("kappa", 'κ'),
+    ("lambda", 'λ'),
+    ("mu", 'μ'),
+    ("nu", 'ν'),
+    ("xi", 'ξ'),
+    ("pi", 'π'),
+    ("varpi", 'ϖ'),
+    ("rho", 'ρ'),
+    ("varrho", 'ϱ'),
+    ("sigma", 'σ'),
+    ("varsigma", 'ς'),
+    ("tau", 'τ'),
+    ("upsilon", 'υ'),
+    ("phi", 'ϕ'),
+    ("varphi", 'φ'),
+    ("chi", 'χ'),
+    ("psi", 'ψ'),
+    ("omega", 'ω'),
+    ("Gamma", 'Γ'),
+    ("Delta", 'Δ'),
+    ("Theta", 'Θ'),
+    ("Lambda", 'Λ'),
+    ("Xi", 'Ξ'),
+    ("Pi", 'Π'),
+    ("Sigma", 'Σ'),
+    ("Upsilon", 'Υ'),
+    ("Phi", 'Φ'),
+    ("Psi", 'Ψ'),
+    ("Omega", 'Ω'),
+    ("infty", '∞'),
+    ("partial", '∂'),
+    ("nabla", '∇'),
+    ("emptyset", '∅'),
+    ("hbar", 'ℏ'),
+    ("ell", 'ℓ'),
+    ("aleph", 'ℵ'),
+];
+
+/// Symbols, as operators.
+const OPERATORS: &[(&str, char)] = &[
+    ("cdot", '⋅'),
+    ("times", '×'),
+    ("div", '÷'),
+    ("pm", '±'),
+    ("mp", '∓'),
+    ("ast", '∗'),
+    ("star", '⋆'),
+    ("circ", '∘'),
+    ("bullet", '∙'),
+    ("leq", '≤'),
+    ("le", '≤'),
+    ("geq", '≥'),
+    ("ge", '≥'),
+    ("neq", '≠'),
+    ("ne", '≠'),
+    ("ll", '≪'),
+    ("gg", '≫'),
+    ("approx", '≈'),
+    ("equiv", '≡'),
+    ("sim", '∼'),
+    ("simeq", '≃'),
+    ("cong", '≅'),
+    ("propto", '∝'),
+    ("to", '→'),
+    ("rightarrow", '→'),
+    ("leftarrow", '←'),
+    ("gets", '←'),
+    ("leftrightarrow", '↔'),
+    ("Rightarrow", '⇒'),
+    ("Leftarrow", '⇐'),
+    ("Leftrightarrow", '⇔'),
+    ("implies", '⟹'),
+    ("iff", '⟺'),
+    ("mapsto", '↦'),
+    ("in", '∈'),
+    ("notin", '∉'),
+    ("ni", '∋'),
+    ("subset", '⊂'),
+    ("subseteq", '⊆'),
+    ("supset", '⊃'),
+    ("supseteq", '⊇'),
+    ("cup", '∪'),
+    ("cap", '∩'),
+    ("setminus", '∖'),
+    ("forall", '∀'),
+    ("exists", '∃'),
+    ("neg", '¬'),
+    ("lnot", '¬'),
+    ("land", '∧'),
+    ("wedge", '∧'),
+    ("lor", '∨'),
+    ("vee", '∨'),
+    ("oplus", '⊕'),
+    ("otimes", '⊗'),
+    ("odot", '⊙'),
+    ("perp", '⊥'),
+    ("parallel", '∥'),
+    ("mid", '∣'),
+    ("angle", '∠'),
+    ("ldots", '…'),
+    ("dots", '…'),
+    ("cdots", '⋯'),
+    ("vdots", '⋮'),
+    ("ddots", '⋱'),
+    ("prime", '′'),
+    ("langle", '⟨'),
+    ("rangle", '⟩'),
+    ("lfloor", '⌊'),
+    ("rfloor", '⌋'),
+    ("lceil", '⌈'),
+    ("rceil", '⌉'),
+    ("lbrace", '{'),
+    ("rbrace", '}'),
+    ("vert", '|'),
+    ("Vert", '‖'),
+    ("{", '{'),
+    ("}", '}'),
+    ("|", '‖'),
+    ("%", '%'),
+    ("$", '$'),
+    ("#", '#'),
+    ("&", '&'),
+    ("_", '_'),
+];
+
+/// Large operators whose scripts are placed under and above them.
+const LARGE_OPERATORS: &[(&str, char)] = &[
+    ("sum", '∑'),
+    ("prod", '∏'),
+    ("coprod", '∐'),
+    ("bigcup", '⋃'),
+    ("bigcap", '⋂'),
+    ("bigoplus", '⨁'),
+    ("bigotimes", '⨂'),
+];
+
+/// Integrals, whose scripts are placed beside them.
+const INTEGRALS: &[(&str, char)] =
+    &[("int", '∫'), ("iint", '∬'), ("iiint", '∭'), ("oint", '∮')];
+
+/// Named functions, such as `\sin`.
+const FUNCTIONS: &[&str] = &[
+    "sin", "cos", "tan", "cot", "sec", "csc", "arcsin", "arccos", "arctan",
+    "sinh", "cosh", "tanh", "log", "ln", "lg", "exp", "det", "dim", "ker",
+    "arg", "deg", "gcd", "hom", "mod",
+];
+
+/// Named functions whose scripts are placed under them, such as `\lim`.
+const LIMIT_FUNCTIONS: &[&str] =
+    &["lim", "max", "min", "sup", "inf", "Pr", "liminf", "limsup"];
+
+/// Accents over (or under) an argument.
+const ACCENTS: &[(&str, char, &str)] = &[
+    ("hat", '^', "mover"),
+    ("widehat", '^', "mover"),
+    ("bar", '¯', "mover"),
+    ("overline", '¯', "mover"),
+    ("vec", '→', "mover"),
+    ("dot", '˙', "mover"),
+    ("ddot", '¨', "mover"),
+    ("tilde", '~', "mover"),
+    ("widetilde", '~', "mover"),
+    ("underline", '_', "munder"),
+];
+
+/// Font commands and their variants.
+const FONTS: &[(&str, &str)] = &[
+    ("mathrm", "normal"),
+    ("mathbf", "bold"),
+    ("mathit", "italic"),
+    ("mathbb", "double-struck"),
+    ("mathcal", "script"),
+    ("mathfrak", "fraktur"),
+    ("mathsf", "sans-serif"),
+    ("mathtt", "monospace"),
+];
+
+/// Spacing commands and their widths.
+const SPACES: &[(&str, &str)] = &[
+    (",", "0.1667em"),
+    (":", "0.2222em"),
+    (">", "0.2222em"),
+    (";", "0.2778em"),
+    (" ", "0.3333em"),
+    ("!", "-0.1667em"),
+    ("quad", "1em"),
+    ("qquad", "2em"),
+];
+
+/// A token of the TeX input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    /// A command, such as `\frac` or `\,`.
+    Command(String),
+    /// A plain character.
+    Char(char),
+    /// Whitespace, significant only in text.
+    Space,
+    /// `{`.
+    Open,
+    /// `}`.
+    Close,
+    /// `^`.
+    Sup,
+    /// `_`.
+    Sub,
+    /// `&`, the separator of cells.
+    Amp,
+    /// `\\`, the separator of rows.
+    RowEnd,
+}
+
+/// Splits the input into tokens, skipping `%` comments.
+fn tokenize(tex: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = tex.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        let token = match ch {
+            '{' => Token::Open,
+            '}' => Token::Close,
+            '^' => Token::Sup,
+            '_' => Token::Sub,
+            '&' => Token::Amp,
+            '%' => {
+                chars.by_ref().find(|&ch| ch == '\n');
+                continue;
+            },
+            '\\' => match chars.next() {
+                Some('\\') => Token::RowEnd,
+                Some(ch) if ch.is_ascii_alphabetic() => {
+                    let mut name = ch.to_string();
+                    while let Some(&ch) =
+                        chars.peek().filter(|ch| ch.is_ascii_alphabetic())
+                    {
+                        name.push(ch);
+                        chars.next();
+                    }
+                    Token::Command(name)
+                },
+                Some(ch) => Token::Command(ch.to_string()),
+                None => Token::Char('\\'),
+            },
+            ch if ch.is_whitespace() => Token::Space,
+            ch => Token::Char(ch),
+        };
+        tokens.push(token);
+    }
+
+    tokens
+}
+
+/// Escapes text for the MathML output.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match html_escape(ch) {
+            Some(escape) => escaped.push_str(escape),
+            None => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+/// Wraps elements in a row, unless there is a single one.
+fn wrap(mut elements: Vec<String>) -> String {
+    if elements.len() == 1 {
+        elements.pop().unwrap()
+    } else {
+        format!("<mrow>{}</mrow>", elements.concat())
+    }
+}
+
+/// A stretchy delimiter, possibly empty (written as `.`).
+fn fence(delimiter: &str) -> String {
+    if delimiter.is_empty() {
+        String::new()
+    } else {
+        format!(
+            "<mo fence=\"true\" stretchy=\"true\">{}</mo>",
+            escape(delimiter)
+        )
+    }
+}
+
+/// Finds a character in a table of commands.
+fn lookup<T>(table: &[(&str, T)], name: &str) -> Option<T>
+where
+    T: Copy,
+{
+    table.iter().find(|(key, _)| *key == name).map(|&(_, value)| value)
+}
+
+/// The parser over the tokens.
+#[derive(Debug, Clone)]
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn skip_spaces(&mut self) {
+        while self.peek() == Some(&Token::Space) {
+            self.pos += 1;
+        }
+    }
+
+    /// Parses elements until the end of a row, a cell, a group, or until
+    /// `\right` and `\end`.
+    fn parse_row(&mut self) -> Result<String, TexError> {
+        let mut elements = Vec::new();
+        while let Some(element) = self.parse_term()? {
+            elements.push(element);
+        }
+        Ok(wrap(elements))
+    }
+
+    /// Parses an element followed by its scripts. `None` at the end of a row.
+    fn parse_term(&mut self) -> Result<Option<String>, TexError> {
+        self.skip_spaces();
+        let (base, limits) = match self.peek() {
+            None | Some(Token::Close) | Some(Token::Amp)
+            | Some(Token::RowEnd) => return Ok(None),
+            Some(Token::Command(name)) if name == "right" || name == "end" => {
+                return Ok(None)
+            },
+            Some(Token::Sup) | Some(Token::Sub) => {
+                ("<mrow></mrow>".into(), false)
+            },
+            Some(_) => self.parse_atom(false)?,
+        };
+        self.parse_scripts(base, limits).map(Some)
+    }
+
+    /// Parses the subscripts and superscripts of the given base.
+    fn parse_scripts(
+        &mut self,
+        mut base: String,
+        limits: bool,
+    ) -> Result<String, TexError> {
+        let mut sub = None;
+        let mut sup = None;
+
+        loop {
+            self.skip_spaces();
+            match self.peek() {
+                Some(Token::Sub) if sub.is_none() => {
+                    self.pos += 1;
+                    sub = Some(self.parse_argument("_")?);
+                },
+                Some(Token::Sup) if sup.is_none() => {
+                    self.pos += 1;
+                    sup = Some(self.parse_argument("^")?);
+                },
+                Some(Token::Char('\'')) if sup.is_none() => {
+                    self.pos += 1;
+                    base = format!("<msup>{}<mo>′</mo></msup>", base);
+                },
+                _ => break,
+            }
+        }
+
+        let (under, over, both) = if limits {
+            ("munder", "mover", "munderover")
+        } else {
+            ("msub", "msup", "msubsup")
+        };
+        Ok(match (sub, sup) {
+            (None, None) => base,
+            (Some(sub), None) => format!("<{0}>{1}{2}</{0}>", under, base, sub),
+            (None, Some(sup)) => format!("<{0}>{1}{2}</{0}>", over, base, sup),
+            (Some(sub), Some(sup)) => {
+                format!("<{0}>{1}{2}{3}</{0}>", both, base, sub, sup)
+            },
+        })
+    }
+
+    /// Parses the mandatory argument of a command, either a group or a single
+    /// element.
+    fn parse_argument(&mut self, command: &str) -> Result<String, TexError> {
+        self.skip_spaces();
+        match self.peek() {
+            Some(Token::Char(_))
+            | Some(Token::Open)
+            | Some(Token::Command(_)) => Ok(self.parse_atom(true)?.0),
+            _ => Err(TexError::MissingArgument(command.to_owned())),
+        }
+    }
+
+    /// Parses the mandatory argument of a command as raw text.
+    fn parse_text(&mut self, command: &str) -> Result<String, TexError> {
+        self.skip_spaces();
+        let mut text = String::new();
+        match self.next() {
+            Some(Token::Open) => (),
+            Some(Token::Char(ch)) => return Ok(ch.to_string()),
+            _ => Err(TexError::MissingArgument(command.to_owned()))?,
+        }
+
+        let mut depth = 0;
+        loop {
+            match self.next().ok_or(TexError::Expected("}"))? {
+                Token::Open => {
+                    depth += 1;
+                    text.push('{');
+                },
+                Token::Close if depth == 0 => break Ok(text),
+                Token::Close => {
+                    depth -= 1;
+                    text.push('}');
+                },
+                Token::Char(ch) => text.push(ch),
+                Token::Space => text.push(' '),
+                Token::Sup => text.push('^'),
+                Token::Sub => text.push('_'),
+                Token::Amp => text.push('&'),
+                Token::RowEnd => text.push('\n'),
+                Token::Command(name) => match lookup(OPERATORS, &name) {
+                    Some(ch) if !name.chars().all(char::is_alphabetic) => {
+                        text.push(ch)
+                    },
+                    _ => {
+                        text.push('\\');
+                        text.push_str(&name);
+                    },
+                },
+            }
+        }
+    }
+
+    /// Parses a delimiter after `\left` or `\right`.
+    fn parse_delimiter(&mut self, command: &str) -> Result<String, TexError> {
+        self.skip_spaces();
+        match self.next() {
+            Some(Token::Char('.')) => Ok(String::new()),
+            Some(Token::Char(ch)) => Ok(ch.to_string()),
+            Some(Token::Command(name)) => lookup(OPERATORS, &name)
+                .map(|ch| ch.to_string())
+                .ok_or(TexError::UnknownCommand(name)),
+            _ => Err(TexError::MissingArgument(command.to_owned())),
+        }
+    }
+
+    /// Parses a single element. If `single` is set, numbers are split into
+    /// digits, like arguments in TeX. Returns whether scripts should be placed
+    /// under and above the element.
+    fn parse_atom(&mut self, single: bool) -> Result<(String, bool), TexError> {
+        let element = match self.next() {
+            Some(Token::Open) => {
+                let row = self.parse_row()?;
+                if self.next() != Some(Token::Close) {
+                    Err(TexError::Expected("}"))?;
+                }
+                row
+            },
+            Some(Token::Char(ch)) if ch.is_ascii_digit() || ch == '.' => {
+                let mut number = ch.to_string();
+                if !single {
+                    while let Some(&Token::Char(ch)) = self.peek() {
+                        if !ch.is_ascii_digit() && ch != '.' {
+                            break;
+                        }
+                        number.push(ch);
+                        self.pos += 1;
+                    }
+                }
+                format!("<mn>{}</mn>", number)
+            },
+            Some(Token::Char(ch)) if ch.is_alphabetic() => {
+                format!("<mi>{}</mi>", ch)
+            },
+            Some(Token::Char('\'')) => "<mo>′</mo>".to_owned(),
+            Some(Token::Char(ch)) => {
+                format!("<mo>{}</mo>", escape(&ch.to_string()))
+            },
+            Some(Token::Command(name)) => return self.parse_command(name),
+            Some(Token::Close) => Err(TexError::Unexpected("}"))?,
+            Some(Token::Amp) => Err(TexError::Unexpected("&"))?,
+            Some(Token::RowEnd) => Err(TexError::Unexpected("\\\\"))?,
+            Some(Token::Sup) => Err(TexError::Unexpected("^"))?,
+            Some(Token::Sub) => Err(TexError::Unexpected("_"))?,
+            Some(Token::Space) | None => Err(TexError::Expected("an element"))?,
+        };
+        Ok((element, false))
+    }
+
+    /// Parses the rest of a command, after its name.
+    fn parse_command(
+        &mut self,
+        name: String,
+    ) -> Result<(String, bool), TexError> {
+        if let Some(ch) = lookup(IDENTIFIERS, &name) {
+            return Ok((format!("<mi>{}</mi>", ch), false));
+        }
+        if let Some(ch) = lookup(OPERATORS, &name) {
+            return Ok((
+                format!("<mo>{}</mo>", escape(&ch.to_string())),
+                false,
+            ));
+        }
+        if let Some(ch) = lookup(LARGE_OPERATORS, &name) {
+            return Ok((format!("<mo largeop=\"true\">{}</mo>", ch), true));
+        }
+        if let Some(ch) = lookup(INTEGRALS, &name) {
+            return Ok((format!("<mo largeop=\"true\">{}</mo>", ch), false));
+        }
+        if let Some(width) = lookup(SPACES, &name) {
+            return Ok((
+                format!("<mspace width=\"{}\"></mspace>", width),
+                false,
+            ));
+        }
+        let limits = LIMIT_FUNCTIONS.contains(&name.as_str());
+        if limits || FUNCTIONS.contains(&name.as_str()) {
+            let element = format!("<mi>{}</mi><mo>&#x2061;</mo>", name);
+            return Ok((format!("<mrow>{}</mrow>", element), limits));
+        }
+        if let Some((_, accent, tag)) =
+            ACCENTS.iter().find(|(accent, _, _)| *accent == name)
+        {
+            let argument = self.parse_argument(&name)?;
+            let element = format!(
+                "<{0} accent=\"true\">{1}<mo>{2}</mo></{0}>",
+                tag, argument, accent
+            );
+            return Ok((element, false));
+        }
+        if let Some(variant) = lookup(FONTS, &name) {
+            let text = self.parse_text(&name)?;
+            let element = format!(
+                "<mi mathvariant=\"{}\">{}</mi>",
+                variant,
+                escape(&text)
+            );
+            return Ok((element, false));
+        }
+
+        let element = match name.as_str() {
+            "frac" | "dfrac" | "tfrac" => {
+                let numerator = self.parse_argument(&name)?;
+                let denominator = self.parse_argument(&name)?;
+                format!("<mfrac>{}{}</mfrac>", numerator, denominator)
+            },
+            "binom" => {
+                let top = self.parse_argument(&name)?;
+                let bottom = self.parse_argument(&name)?;
+                format!(
+                    "<mrow><mo>(</mo><mfrac \
+                     linethickness=\"0\">{}{}</mfrac><mo>)</mo></mrow>",
+                    top, bottom
+                )
+            },
+            "sqrt" => {
+                self.skip_spaces();
+                let index = if self.peek() == Some(&Token::Char('[')) {
+                    self.pos += 1;
+                    let mut elements = Vec::new();
+                    loop {
+                        self.skip_spaces();
+                        match self.peek() {
+                            Some(Token::Char(']')) => break,
+                            None => Err(TexError::Expected("]"))?,
+                            _ => elements.push(self.parse_atom(false)?.0),
+                        }
+                    }
+                    self.pos += 1;
+                    Some(wrap(elements))
+                } else {
+                    None
+                };
+                let radicand = self.parse_argument(&name)?;
+                match index {
+                    Some(index) => {
+                        format!("<mroot>{}{}</mroot>", radicand, index)
+                    },
+                    None => format!("<msqrt>{}</msqrt>", radicand),
+                }
+            },
+            "text" | "textrm" | "textit" | "textbf" | "mbox" => {
+                let text = self.parse_text(&name)?;
+                format!("<mtext>{}</mtext>", escape(&text))
+            },
+            "operatorname" => {
+                let text = self.parse_text(&name)?;
+                format!(
+                    "<mrow><mi>{}</mi><mo>&#x2061;</mo></mrow>",
+                    escape(&text)
+                )
+            },
+            "left" => {
+                let open = self.parse_delimiter(&name)?;
+                let row = self.parse_row()?;
+                if self.next() != Some(Token::Command("right".to_owned())) {
+                    Err(TexError::Expected("\\right"))?;
+                }
+                let close = self.parse_delimiter("right")?;
+                format!("<mrow>{}{}{}</mrow>", fence(&open), row, fence(&close))
+            },
+            "begin" => self.parse_environment()?,
+            _ => Err(TexError::UnknownCommand(name))?,
+        };
+        Ok((element, false))
+    }
+
+    /// Parses an environment, after `\begin`.
+    fn parse_environment(&mut self) -> Result<String, TexError> {
+        let env = self.parse_text("begin")?;
+        let (open, close, align) = match env.as_str() {
+            "matrix" | "smallmatrix" => ("", "", None),
+            "pmatrix" => ("(", ")", None),
+            "bmatrix" => ("[", "]", None),
+            "Bmatrix" => ("{", "}", None),
+            "vmatrix" => ("|", "|", None),
+            "Vmatrix" => ("‖", "‖", None),
+            "cases" => ("{", "", Some("left left")),
+            "aligned" | "align" | "align*" | "split" => {
+                ("", "", Some("right left"))
+            },
+            _ => Err(TexError::UnknownEnvironment(env.clone()))?,
+        };
+
+        let mut table = String::from("<mtable");
+        if let Some(align) = align {
+            let _ = write!(table, " columnalign=\"{}\"", align);
+        }
+        table.push_str("><mtr>");
+
+        loop {
+            let cell = self.parse_row()?;
+            let _ = write!(table, "<mtd>{}</mtd>", cell);
+            match self.next() {
+                Some(Token::Amp) => (),
+                Some(Token::RowEnd) => table.push_str("</mtr><mtr>"),
+                Some(Token::Command(name)) if name == "end" => {
+                    if self.parse_text("end")? != env {
+                        Err(TexError::Expected("matching \\end"))?;
+                    }
+                    break;
+                },
+                _ => Err(TexError::Expected("\\end"))?,
+            }
+        }
+        table.push_str("</mtr></mtable>");
+
+        Ok(format!("<mrow>{}{}{}</mrow>", fence(open), table, fence(close)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{to_mathml, TexError};
+
+    #[test]
+    fn scripts_and_fractions() {
+        assert_eq!(
+            to_mathml(r"\frac{a}{2} + x_i^{10}").unwrap(),
+            "<mrow><mfrac><mi>a</mi><mn>2</mn></mfrac><mo>+</\
+             mo><msubsup><mi>x</mi><mi>i</mi><mn>10</mn></msubsup></mrow>"
+        );
+        assert_eq!(
+            to_mathml(r"\sum_{n=1}^N n").unwrap(),
+            "<mrow><munderover><mo \
+             largeop=\"true\">∑</mo><mrow><mi>n</mi><mo>=</mo><mn>1</mn></\
+             mrow><mi>N</mi></munderover><mi>n</mi></mrow>"
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            to_mathml(r"\foo x"),
+            Err(TexError::UnknownCommand("foo".to_owned()))
+        );
+        assert_eq!(to_mathml(r"{x"), Err(TexError::Expected("}")));
+        assert_eq!(to_mathml(r"x}"), Err(TexError::Unexpected("}")));
+    }
+}
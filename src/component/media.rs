@@ -0,0 +1,66 @@
+//! This module provides helpers shared by audio and video components.
+
+use crate::{component::Context, location::Location};
+use std::fmt;
+
+/// The kind of a media file, used to infer its MIME type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) enum MediaKind {
+    /// An audio file.
+    Audio,
+    /// A video file.
+    Video,
+}
+
+impl MediaKind {
+    /// The MIME type of a file of this kind with the given extension.
+    fn media_type(self, extension: &str) -> Option<&'static str> {
+        let extension = extension.to_ascii_lowercase();
+        match (self, extension.as_str()) {
+            (MediaKind::Audio, "mp3") => Some("audio/mpeg"),
+            (MediaKind::Audio, "ogg" | "oga" | "opus") => Some("audio/ogg"),
+            (MediaKind::Audio, "wav") => Some("audio/wav"),
+            (MediaKind::Audio, "flac") => Some("audio/flac"),
+            (MediaKind::Audio, "m4a" | "aac") => Some("audio/mp4"),
+            (MediaKind::Audio, "weba") => Some("audio/webm"),
+            (MediaKind::Video, "mp4" | "m4v") => Some("video/mp4"),
+            (MediaKind::Video, "webm") => Some("video/webm"),
+            (MediaKind::Video, "ogv" | "ogg") => Some("video/ogg"),
+            (MediaKind::Video, "mov") => Some("video/quicktime"),
+            (MediaKind::Video, "mkv") => Some("video/x-matroska"),
+            _ => None,
+        }
+    }
+}
+
+/// Writes the `<source>` elements of an audio or video, with the MIME type
+/// inferred from the extension of each file.
+pub(crate) fn write_sources(
+    fmt: &mut fmt::Formatter,
+    ctx: Context,
+    kind: MediaKind,
+    sources: &[Location],
+) -> fmt::Result {
+    for src in sources {
+        write!(fmt, "<source src=\"{}\"", ctx.renderer(src))?;
+        if let Some(typ) = src.extension().and_then(|ext| kind.media_type(ext))
+        {
+            write!(fmt, " type=\"{}\"", typ)?;
+        }
+        write!(fmt, ">")?;
+    }
+    Ok(())
+}
+
+/// Writes a link to the first file of an audio or video, for browsers without
+/// support for it.
+pub(crate) fn write_fallback(
+    fmt: &mut fmt::Formatter,
+    ctx: Context,
+    sources: &[Location],
+) -> fmt::Result {
+    if let Some(src) = sources.first() {
+        write!(fmt, "<a href=\"{0}\">{0}</a>", ctx.renderer(src))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,117 @@
+//! This module exports footnote components. Footnotes are collected by the page
+//! and displayed in a "Notes" section after every other section.
+
+use crate::{
+    component::{
+        BlockComponent,
+        Component,
+        Context,
+        DynComponent,
+        InlineComponent,
+    },
+    location::Id,
+};
+use anyhow::anyhow;
+use std::fmt;
+
+/// A footnote, rendered as a numbered superscript link to the note's text. A
+/// named footnote can be referred to again with [`Footnote::reuse`], pointing
+/// to the same number.
+#[derive(Debug, Clone)]
+pub struct Footnote {
+    /// Name of the note, used to refer to it again.
+    pub name: Option<Id>,
+    /// Text of the note. `None` when reusing a named note.
+    pub body: Option<DynComponent<InlineComponent>>,
+}
+
+impl Footnote {
+    /// Creates an anonymous footnote with the given text.
+    pub fn new<T>(body: T) -> Self
+    where
+        T: Component<Kind = InlineComponent> + Send + Sync + 'static,
+    {
+        Self { name: None, body: Some(body.to_dyn()) }
+    }
+
+    /// Creates a named footnote with the given text.
+    pub fn named<T>(name: Id, body: T) -> Self
+    where
+        T: Component<Kind = InlineComponent> + Send + Sync + 'static,
+    {
+        Self { name: Some(name), body: Some(body.to_dyn()) }
+    }
+
+    /// Refers again to a named footnote of the same page. The note may be
+    /// defined after this reference.
+    pub fn reuse(name: Id) -> Self {
+        Self { name: Some(name), body: None }
+    }
+}
+
+impl Component for Footnote {
+    type Kind = InlineComponent;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        if let (Some(name), None) = (&self.name, &self.body) {
+            if ctx.is_final_pass() && !ctx.state.has_note_body(name) {
+                Err(ctx.report(anyhow!("Unknown footnote {}", name)))?;
+            }
+        }
+        let (number, occurrence) =
+            ctx.state.add_note(self.name.as_ref(), self.body.as_ref());
+        write!(fmt, "<sup class=\"footnote-ref\" id=\"fnref-{}", number)?;
+        if occurrence > 1 {
+            write!(fmt, "-{}", occurrence)?;
+        }
+        write!(fmt, "\"><a href=\"#fn-{0}\">[{0}]</a></sup>", number)?;
+        Ok(())
+    }
+}
+
+/// A footnote collected in a page. Internal use.
+#[derive(Debug, Clone)]
+pub(crate) struct Note {
+    /// Name of the note, if any.
+    pub(crate) name: Option<Id>,
+    /// Text of the note, if already known.
+    pub(crate) body: Option<DynComponent<InlineComponent>>,
+    /// How many times the note was referred to.
+    pub(crate) refs: u32,
+}
+
+/// The list of footnotes collected in the current page. Internal use.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Notes;
+
+impl Component for Notes {
+    type Kind = BlockComponent;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        write!(fmt, "<ol class=\"footnotes\">")?;
+
+        // Notes may refer to other notes, so they may grow while rendered.
+        let mut index = 0;
+        while let Some(note) = ctx.state.note(index) {
+            let number = index + 1;
+            write!(
+                fmt,
+                "<li id=\"fn-{}\" class=\"footnote\">{}",
+                number,
+                ctx.renderer(&note.body),
+            )?;
+            for occurrence in 1 ..= note.refs {
+                write!(fmt, " <a href=\"#fnref-{}", number)?;
+                if occurrence > 1 {
+                    write!(fmt, "-{}", occurrence)?;
+                }
+                write!(fmt, "\" class=\"footnote-back\">↑</a>")?;
+            }
+            write!(fmt, "</li>")?;
+            index += 1;
+        }
+
+        write!(fmt, "</ol>")?;
+        Ok(())
+    }
+}
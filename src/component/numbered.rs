@@ -0,0 +1,51 @@
+//! This module exports references to numbered components, such as equations
+//! and examples.
+
+use crate::{
+    component::{Component, Context, InlineComponent},
+    location::InternalLoc,
+};
+use anyhow::anyhow;
+use std::fmt;
+
+/// A reference to a component numbered with the given counter, displayed as
+/// its number and linking to it. The component may be in another page.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NumberRef {
+    /// The counter numbering the component, such as `"equation"`.
+    pub counter: &'static str,
+    /// Location of the component.
+    pub location: InternalLoc,
+}
+
+impl NumberRef {
+    /// Creates a reference to the component at the given location, numbered
+    /// with the given counter.
+    pub fn new(counter: &'static str, location: InternalLoc) -> Self {
+        Self { counter, location }
+    }
+}
+
+impl Component for NumberRef {
+    type Kind = InlineComponent;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        write!(
+            fmt,
+            "<a href=\"{}\" class=\"{}-ref\">(",
+            ctx.renderer(&self.location),
+            self.counter
+        )?;
+        match ctx.number_of(&self.location) {
+            Some(number) => write!(fmt, "{}", number)?,
+            None if ctx.is_final_pass() => Err(ctx.report(anyhow!(
+                "Unknown {} {}",
+                self.counter,
+                self.location
+            )))?,
+            None => write!(fmt, "?")?,
+        }
+        write!(fmt, ")</a>")?;
+        Ok(())
+    }
+}
@@ -0,0 +1,342 @@
+//! This module exports a component for hand-written HTML markup.
+
+use crate::{
+    component::{Component, Context, InlineComponent},
+    location::InternalLoc,
+};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    fmt,
+};
+
+/// Raw HTML markup, written into the page without escaping. Links in `href`
+/// and `src` attributes starting with a single `/` are internal locations (e.g.
+/// `/langs/div-prt#vowels`) and are rewritten relative to the current page.
+/// The component is inline; use [`Component::blocking`] for block markup.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RawHtml {
+    markup: String,
+}
+
+impl RawHtml {
+    /// Creates the component from trusted markup, which is passed through as
+    /// is.
+    pub fn trusted<S>(markup: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self { markup: markup.into() }
+    }
+
+    /// Creates the component from untrusted markup. The markup is parsed and
+    /// only the tags and attributes in the given allowlist are kept.
+    pub fn sanitized<S>(markup: S, allowlist: &Allowlist) -> Self
+    where
+        S: AsRef<str>,
+    {
+        Self { markup: allowlist.clean(markup.as_ref()) }
+    }
+
+    /// The markup of this component.
+    pub fn as_str(&self) -> &str {
+        &self.markup
+    }
+}
+
+impl Component for RawHtml {
+    type Kind = InlineComponent;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        let mut rest = self.markup.as_str();
+
+        while let Some(start) = rest.find('<') {
+            let end = match TagLinks::parse(&rest[start ..]) {
+                Some(links) => {
+                    let mut copied = 0;
+                    for link in links.spans {
+                        let value =
+                            &rest[start + link.start .. start + link.end];
+                        let loc = value
+                            .strip_prefix('/')
+                            .filter(|path| !path.starts_with('/'))
+                            .and_then(|path| InternalLoc::parse(path).ok());
+                        if let Some(loc) = loc {
+                            fmt.write_str(&rest[copied .. start + link.outer])?;
+                            write!(
+                                fmt,
+                                "\"{}\"",
+                                ctx.renderer(loc.relative_url(ctx.location()))
+                            )?;
+                            copied = start + link.outer_end;
+                        }
+                    }
+                    fmt.write_str(&rest[copied .. start + links.len])?;
+                    start + links.len
+                },
+                None => {
+                    fmt.write_str(&rest[.. start + 1])?;
+                    start + 1
+                },
+            };
+            rest = &rest[end ..];
+        }
+
+        fmt.write_str(rest)?;
+        Ok(())
+    }
+}
+
+/// Allowlist of tags and attributes kept by [`RawHtml::sanitized`]. Anything
+/// else is removed; the contents of removed tags are kept, except for scripts
+/// and styles. Dangerous URL schemes are always removed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Allowlist {
+    /// Tags allowed in the markup.
+    pub tags: BTreeSet<String>,
+    /// Attributes allowed in any tag.
+    pub attributes: BTreeSet<String>,
+    /// Attributes allowed only in specific tags, indexed by tag.
+    pub tag_attributes: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl Allowlist {
+    /// Creates an allowlist that does not allow anything at all. Only text is
+    /// kept.
+    pub fn empty() -> Self {
+        Self {
+            tags: BTreeSet::new(),
+            attributes: BTreeSet::new(),
+            tag_attributes: BTreeMap::new(),
+        }
+    }
+
+    /// Allows the given tag. Returns the modified allowlist.
+    pub fn tag<S>(mut self, tag: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.tags.insert(tag.into());
+        self
+    }
+
+    /// Allows the given attribute in any tag. Returns the modified allowlist.
+    pub fn attribute<S>(mut self, attribute: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.attributes.insert(attribute.into());
+        self
+    }
+
+    /// Allows the given attribute in the given tag. Returns the modified
+    /// allowlist.
+    pub fn tag_attribute<S, A>(mut self, tag: S, attribute: A) -> Self
+    where
+        S: Into<String>,
+        A: Into<String>,
+    {
+        self.tag_attributes
+            .entry(tag.into())
+            .or_default()
+            .insert(attribute.into());
+        self
+    }
+
+    /// Sanitizes the given markup.
+    fn clean(&self, markup: &str) -> String {
+        let tags = self.tags.iter().map(String::as_str).collect::<HashSet<_>>();
+        let attributes =
+            self.attributes.iter().map(String::as_str).collect::<HashSet<_>>();
+        let tag_attributes = self
+            .tag_attributes
+            .iter()
+            .map(|(tag, attrs)| {
+                (tag.as_str(), attrs.iter().map(String::as_str).collect())
+            })
+            .collect::<HashMap<_, HashSet<_>>>();
+
+        let mut builder = ammonia::Builder::empty();
+        builder
+            .rm_clean_content_tags(&tags)
+            .rm_clean_content_tags(tag_attributes.keys())
+            .tags(tags)
+            .generic_attributes(attributes)
+            .tag_attributes(tag_attributes)
+            .link_rel(None)
+            .url_schemes(["http", "https", "mailto"].iter().copied().collect());
+        builder.clean(markup).to_string()
+    }
+}
+
+impl Default for Allowlist {
+    /// Allows common text formatting, lists, tables, images and links.
+    fn default() -> Self {
+        let tags = "a abbr b bdi bdo blockquote br caption cite code col \
+                    colgroup dd del details dfn div dl dt em figcaption \
+                    figure h1 h2 h3 h4 h5 h6 hr i img ins kbd li mark ol p \
+                    pre q rp rt ruby s samp small span strong sub summary sup \
+                    table tbody td tfoot th thead tr u ul var wbr";
+        let attributes = ["class", "dir", "id", "lang", "title"];
+        let tag_attributes = [
+            ("a", "href"),
+            ("abbr", "title"),
+            ("col", "span"),
+            ("colgroup", "span"),
+            ("img", "alt"),
+            ("img", "height"),
+            ("img", "src"),
+            ("img", "width"),
+            ("ol", "reversed"),
+            ("ol", "start"),
+            ("ol", "type"),
+            ("td", "colspan"),
+            ("td", "rowspan"),
+            ("th", "colspan"),
+            ("th", "rowspan"),
+            ("th", "scope"),
+        ];
+
+        let mut this = Self::empty();
+        for tag in tags.split_whitespace() {
+            this = this.tag(tag);
+        }
+        for &attribute in &attributes {
+            this = this.attribute(attribute);
+        }
+        for &(tag, attribute) in &tag_attributes {
+            this = this.tag_attribute(tag, attribute);
+        }
+        this
+    }
+}
+
+/// Linking attributes found in an opening tag.
+#[derive(Debug, Clone)]
+struct TagLinks {
+    /// Length of the whole tag, including `<` and `>`.
+    len: usize,
+    /// Values of the `href` and `src` attributes.
+    spans: Vec<LinkSpan>,
+}
+
+/// Position of an attribute value relative to the start of the tag.
+#[derive(Debug, Clone, Copy)]
+struct LinkSpan {
+    /// Start of the value, including quotes.
+    outer: usize,
+    /// End of the value, including quotes.
+    outer_end: usize,
+    /// Start of the value, without quotes.
+    start: usize,
+    /// End of the value, without quotes.
+    end: usize,
+}
+
+impl TagLinks {
+    /// Parses an opening tag at the start of the given markup. `None` if this
+    /// is not an opening tag.
+    fn parse(markup: &str) -> Option<Self> {
+        let bytes = markup.as_bytes();
+        if !bytes.get(1)?.is_ascii_alphabetic() {
+            return None;
+        }
+
+        let is_name_end =
+            |ch: u8| ch.is_ascii_whitespace() || ch == b'>' || ch == b'/';
+        let skip_space =
+            |i| skip_while(bytes, i, |ch| ch.is_ascii_whitespace());
+
+        let mut i = skip_while(bytes, 1, |ch| !is_name_end(ch));
+        let mut spans = Vec::new();
+
+        loop {
+            i = skip_space(i);
+            match *bytes.get(i)? {
+                b'>' => break Some(Self { len: i + 1, spans }),
+                b'/' => {
+                    i += 1;
+                    continue;
+                },
+                _ => (),
+            }
+
+            let name_start = i;
+            i = skip_while(bytes, i, |ch| !is_name_end(ch) && ch != b'=');
+            let name = &markup[name_start .. i];
+
+            i = skip_space(i);
+            if bytes.get(i) != Some(&b'=') {
+                continue;
+            }
+            i = skip_space(i + 1);
+
+            let outer = i;
+            let (start, end) = match *bytes.get(i)? {
+                quote @ b'"' | quote @ b'\'' => {
+                    i += markup[i + 1 ..].find(quote as char)? + 2;
+                    (outer + 1, i - 1)
+                },
+                _ => {
+                    i = skip_while(bytes, i, |ch| {
+                        !ch.is_ascii_whitespace() && ch != b'>'
+                    });
+                    (outer, i)
+                },
+            };
+
+            if name.eq_ignore_ascii_case("href")
+                || name.eq_ignore_ascii_case("src")
+            {
+                spans.push(LinkSpan { outer, outer_end: i, start, end });
+            }
+        }
+    }
+}
+
+/// Advances the index while the predicate holds for the bytes.
+fn skip_while<F>(bytes: &[u8], mut i: usize, mut predicate: F) -> usize
+where
+    F: FnMut(u8) -> bool,
+{
+    while i < bytes.len() && predicate(bytes[i]) {
+        i += 1;
+    }
+    i
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Allowlist, RawHtml};
+    use crate::component::TestContext;
+
+    #[test]
+    fn trusted_rewrites_internal_links() {
+        let test = TestContext::new("langs/div-prt/phonology");
+        let ctx = test.ctx();
+        let raw = RawHtml::trusted(
+            "<p onclick=x><a href=\"/langs/div-prt#vowels\">a</a> < b \
+             <img src='https://example.com/a.png'><a href=/index.html>c</a>",
+        );
+        assert_eq!(
+            ctx.renderer(&raw).to_string(),
+            "<p onclick=x><a href=\"../../langs/div-prt#vowels\">a</a> < b \
+             <img src='https://example.com/a.png'><a \
+             href=\"../../index.html\">c</a>"
+        );
+    }
+
+    #[test]
+    fn sanitized_keeps_allowlist() {
+        let test = TestContext::new("langs/index.html");
+        let ctx = test.ctx();
+        let raw = RawHtml::sanitized(
+            "<b onclick=\"x()\">bold</b><script>alert(1)</script><a \
+             href=\"javascript:x()\">j</a><a href=\"/langs/div-prt\">l</a>",
+            &Allowlist::default(),
+        );
+        assert_eq!(
+            ctx.renderer(&raw).to_string(),
+            "<b>bold</b><a>j</a><a href=\"../langs/div-prt\">l</a>"
+        );
+    }
+}
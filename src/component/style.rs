@@ -0,0 +1,329 @@
+//! This module exports a wrapper that attaches custom attributes to any
+//! component.
+
+use crate::{
+    component::{BlockComponent, Component, Context, InlineComponent},
+    location::Id,
+};
+use anyhow::anyhow;
+use std::fmt;
+
+/// Direction of the text of a component.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dir {
+    /// Left-to-right text.
+    Ltr,
+    /// Right-to-left text.
+    Rtl,
+    /// Direction determined by the browser from the contents.
+    Auto,
+}
+
+impl Dir {
+    /// The value of the `dir` HTML attribute.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Dir::Ltr => "ltr",
+            Dir::Rtl => "rtl",
+            Dir::Auto => "auto",
+        }
+    }
+}
+
+impl fmt::Display for Dir {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str(self.as_str())
+    }
+}
+
+/// A set of extra HTML attributes attached to an element.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Attributes {
+    /// Extra CSS classes.
+    pub classes: Vec<String>,
+    /// ID used to anchor the element.
+    pub id: Option<Id>,
+    /// Language tag of the contents, such as `"en"` or `"pt-BR"`.
+    pub lang: Option<String>,
+    /// Direction of the text.
+    pub dir: Option<Dir>,
+    /// Pairs of `data-*` attributes. The name is given without the `data-`
+    /// prefix.
+    pub data: Vec<(String, String)>,
+}
+
+impl Attributes {
+    /// Tests if no attribute is set at all.
+    pub fn is_empty(&self) -> bool {
+        self.classes.is_empty()
+            && self.id.is_none()
+            && self.lang.is_none()
+            && self.dir.is_none()
+            && self.data.is_empty()
+    }
+
+    /// Displays the attributes as written in the opening tag of an element,
+    /// after its name, with a leading space. The extra classes are appended
+    /// to the element's own class, if any.
+    pub fn display<'attrs, 'loc, 'site>(
+        &'attrs self,
+        ctx: Context<'loc, 'site>,
+        class: &'attrs str,
+    ) -> DisplayAttributes<'attrs, 'loc, 'site> {
+        DisplayAttributes { attrs: self, ctx, class }
+    }
+
+    /// Merges the attributes of an inner wrapper with the ones of an outer
+    /// wrapper. Classes are concatenated; the other attributes must not be
+    /// set by both.
+    fn merge(&self, outer: &Self) -> anyhow::Result<Self> {
+        let mut merged = self.clone();
+        merged.classes.extend(outer.classes.iter().cloned());
+        if outer.id.is_some() {
+            if merged.id.is_some() {
+                Err(anyhow!("Styled element already has attribute id"))?;
+            }
+            merged.id = outer.id.clone();
+        }
+        if outer.lang.is_some() {
+            if merged.lang.is_some() {
+                Err(anyhow!("Styled element already has attribute lang"))?;
+            }
+            merged.lang = outer.lang.clone();
+        }
+        if outer.dir.is_some() {
+            if merged.dir.is_some() {
+                Err(anyhow!("Styled element already has attribute dir"))?;
+            }
+            merged.dir = outer.dir;
+        }
+        for (name, value) in &outer.data {
+            if merged.data.iter().any(|(other, _)| other == name) {
+                Err(anyhow!(
+                    "Styled element already has attribute data-{}",
+                    name
+                ))?;
+            }
+            merged.data.push((name.clone(), value.clone()));
+        }
+        Ok(merged)
+    }
+}
+
+/// Displays [`Attributes`] in an opening tag, together with the element's own
+/// class. Created by [`Attributes::display`].
+#[derive(Debug, Clone, Copy)]
+pub struct DisplayAttributes<'attrs, 'loc, 'site> {
+    attrs: &'attrs Attributes,
+    ctx: Context<'loc, 'site>,
+    class: &'attrs str,
+}
+
+impl<'attrs, 'loc, 'site> fmt::Display
+    for DisplayAttributes<'attrs, 'loc, 'site>
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let ctx = self.ctx;
+        let classes = Some(self.class)
+            .filter(|class| !class.is_empty())
+            .into_iter()
+            .chain(self.attrs.classes.iter().map(String::as_str));
+        for (i, class) in classes.enumerate() {
+            let prefix = if i == 0 { " class=\"" } else { " " };
+            write!(fmt, "{}{}", prefix, ctx.renderer(class))?;
+        }
+        if !self.class.is_empty() || !self.attrs.classes.is_empty() {
+            write!(fmt, "\"")?;
+        }
+        if let Some(id) = &self.attrs.id {
+            write!(fmt, " id=\"{}\"", ctx.renderer(id))?;
+        }
+        if let Some(lang) = &self.attrs.lang {
+            write!(fmt, " lang=\"{}\"", ctx.renderer(lang))?;
+        }
+        if let Some(dir) = self.attrs.dir {
+            write!(fmt, " dir=\"{}\"", dir)?;
+        }
+        for (name, value) in &self.attrs.data {
+            write!(
+                fmt,
+                " data-{}=\"{}\"",
+                ctx.renderer(name),
+                ctx.renderer(value)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Wraps a component in order to attach extra classes, an ID, language,
+/// direction and `data-*` attributes to it. Components rendering a single
+/// element of their own, such as paragraphs, tables and lists, write the
+/// attributes into it (see [`Component::to_html_with`]). Other components, such
+/// as plain text, are wrapped in a `<span>` element carrying the attributes if
+/// inline, or in a `<div>` if a block.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Styled<T, K>
+where
+    T: Component<Kind = K>,
+{
+    /// The wrapped component.
+    pub component: T,
+    /// The attributes attached to the component.
+    pub attrs: Attributes,
+}
+
+impl<T, K> Styled<T, K>
+where
+    T: Component<Kind = K>,
+{
+    /// Wraps the component with no attributes set.
+    pub fn new(component: T) -> Self {
+        Self { component, attrs: Attributes::default() }
+    }
+
+    /// Appends a CSS class. Returns the modified wrapper.
+    pub fn class<S>(mut self, class: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.attrs.classes.push(class.into());
+        self
+    }
+
+    /// Sets the ID. Returns the modified wrapper.
+    pub fn id(mut self, id: Id) -> Self {
+        self.attrs.id = Some(id);
+        self
+    }
+
+    /// Sets the language tag. Returns the modified wrapper.
+    pub fn lang<S>(mut self, lang: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.attrs.lang = Some(lang.into());
+        self
+    }
+
+    /// Sets the text direction. Returns the modified wrapper.
+    pub fn dir(mut self, dir: Dir) -> Self {
+        self.attrs.dir = Some(dir);
+        self
+    }
+
+    /// Appends a `data-*` attribute. The name is given without the `data-`
+    /// prefix. Returns the modified wrapper.
+    pub fn data<S, V>(mut self, name: S, value: V) -> Self
+    where
+        S: Into<String>,
+        V: Into<String>,
+    {
+        self.attrs.data.push((name.into(), value.into()));
+        self
+    }
+}
+
+impl<T> Component for Styled<T, InlineComponent>
+where
+    T: Component<Kind = InlineComponent>,
+{
+    type Kind = T::Kind;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        self.component.to_html_with(fmt, ctx, &self.attrs, "span")
+    }
+
+    fn to_html_with(
+        &self,
+        fmt: &mut fmt::Formatter,
+        ctx: Context,
+        attrs: &Attributes,
+        wrapper: &str,
+    ) -> fmt::Result {
+        let attrs =
+            self.attrs.merge(attrs).map_err(|error| ctx.report(error))?;
+        self.component.to_html_with(fmt, ctx, &attrs, wrapper)
+    }
+}
+
+impl<T> Component for Styled<T, BlockComponent>
+where
+    T: Component<Kind = BlockComponent>,
+{
+    type Kind = T::Kind;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        self.component.to_html_with(fmt, ctx, &self.attrs, "div")
+    }
+
+    fn to_html_with(
+        &self,
+        fmt: &mut fmt::Formatter,
+        ctx: Context,
+        attrs: &Attributes,
+        wrapper: &str,
+    ) -> fmt::Result {
+        let attrs =
+            self.attrs.merge(attrs).map_err(|error| ctx.report(error))?;
+        self.component.to_html_with(fmt, ctx, &attrs, wrapper)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Dir;
+    use crate::{
+        component::{
+            table::{Entry, Table},
+            text::{Bold, Paragraph},
+            Component,
+            TestContext,
+        },
+        location::Id,
+    };
+    use std::fmt::Write;
+
+    #[test]
+    fn attributes_on_own_element() {
+        let test = TestContext::new("langs/index.html");
+        let ctx = test.ctx();
+
+        let bold = Bold("lupus").styled().class("latin").lang("la");
+        assert_eq!(
+            ctx.renderer(&bold).to_string(),
+            "<b class=\"bold latin\" lang=\"la\">lupus</b>"
+        );
+
+        let text = "lupus".styled().dir(Dir::Ltr).data("word", "1");
+        assert_eq!(
+            ctx.renderer(&text).to_string(),
+            "<span dir=\"ltr\" data-word=\"1\">lupus</span>"
+        );
+
+        let table = Table::new("T", vec![vec![Entry::new("a")]])
+            .styled()
+            .id(Id::new("nouns").unwrap());
+        assert!(ctx.renderer(&table).to_string().starts_with(
+            "<div class=\"table-wrapper\"><table class=\"table\" \
+             id=\"nouns\"><caption"
+        ));
+
+        let paragraph = Paragraph(vec!["a > b".to_dyn(), Bold("c").to_dyn()])
+            .to_dyn()
+            .styled()
+            .class("note")
+            .styled()
+            .id(Id::new("first").unwrap());
+        assert_eq!(
+            ctx.renderer(&paragraph).to_string(),
+            "<p class=\"paragraph note\" id=\"first\">a &gt; b<b \
+             class=\"bold\">c</b></p>"
+        );
+
+        let twice = Bold("a").styled().lang("la").styled().lang("en");
+        let mut html = String::new();
+        assert!(write!(html, "{}", ctx.renderer(&twice)).is_err());
+        assert!(test.state.take_error().is_some());
+    }
+}
@@ -1,19 +1,28 @@
 //! This module exports items related to table components.
 
+pub mod loader;
+
 use crate::component::{
+    style::Attributes,
     BlockComponent,
     Component,
     Context,
     DynComponent,
     InlineComponent,
 };
+use anyhow::anyhow;
 use std::fmt;
+use thiserror::Error;
+
+/// The script making tables sortable and filterable.
+const TABLES_SCRIPT: &str = include_str!("table/tables.js");
 
 /// The type of the entries of a table. For regular tables, this will act like a
 /// Matrix, but for irregular ones, it won't be a proper matrix.
 pub type Entries<E> = Vec<Vec<Entry<E>>>;
 
-/// A table with title and flexible columns.
+/// A table with title and flexible columns. Leading rows made only of header
+/// entries are the header of the table.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Table<T, E>
 where
@@ -26,31 +35,195 @@ where
     pub entries: Entries<E>,
 }
 
-impl<T, E> Component for Table<T, E>
+impl<T, E> Table<T, E>
 where
     T: Component<Kind = InlineComponent>,
     E: Component,
 {
-    type Kind = BlockComponent;
+    /// Creates a table from its title and entries.
+    pub fn new(title: T, entries: Entries<E>) -> Self {
+        Self { title, entries }
+    }
 
-    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+    /// Sets the alignment of the contents of a column, counting from zero.
+    pub fn align(self, column: usize, align: Align) -> TableOptions<T, E> {
+        TableOptions::new(self).align(column, align)
+    }
+
+    /// Makes the columns sortable by the reader.
+    pub fn sortable(self) -> TableOptions<T, E> {
+        TableOptions::new(self).sortable()
+    }
+
+    /// Adds a text box filtering the rows.
+    pub fn filterable(self) -> TableOptions<T, E> {
+        TableOptions::new(self).filterable()
+    }
+
+    /// Number of leading rows made only of header entries.
+    fn header_rows(&self) -> usize {
+        self.entries
+            .iter()
+            .take_while(|row| {
+                !row.is_empty() && row.iter().all(|entry| entry.header)
+            })
+            .count()
+    }
+
+    /// Why the rows of the table cannot be sorted by the script, if they
+    /// cannot: the script maps each cell of a row to the header cell at the
+    /// same position, so it needs a single header row and no span.
+    fn unsortable_reason(&self) -> Option<&'static str> {
+        if self.header_rows() != 1 {
+            Some("it needs exactly one header row")
+        } else if self
+            .entries
+            .iter()
+            .flatten()
+            .any(|entry| entry.rowspan != 1 || entry.colspan != 1)
+        {
+            Some("entries span several rows or columns")
+        } else {
+            None
+        }
+    }
+
+    /// Checks that the entries tile a rectangle: no entry has a zero span,
+    /// overlaps another or spans past the last row, and every row has the
+    /// same width as the first one. Rows and columns count from zero.
+    pub fn validate(&self) -> Result<(), Vec<TableError>> {
+        let (_, errors) = self.layout();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Computes the column at which each entry starts, taking spans into
+    /// account, and finds the errors of the grid.
+    fn layout(&self) -> (Vec<Vec<usize>>, Vec<TableError>) {
+        let height = self.entries.len();
+        let mut grid = vec![Vec::<bool>::new(); height];
+        let mut columns = Vec::with_capacity(height);
+        let mut errors = Vec::new();
+
+        for (row, entries) in self.entries.iter().enumerate() {
+            let mut row_columns = Vec::with_capacity(entries.len());
+            let mut column = 0;
+            for entry in entries {
+                while grid[row].get(column).copied().unwrap_or(false) {
+                    column += 1;
+                }
+                row_columns.push(column);
+
+                if entry.rowspan == 0 || entry.colspan == 0 {
+                    errors.push(TableError::ZeroSpan { row, column });
+                }
+                let rowspan = entry.rowspan.max(1) as usize;
+                let colspan = entry.colspan.max(1) as usize;
+                if row + rowspan > height {
+                    errors.push(TableError::PastEnd { row, column });
+                }
+
+                let mut overlaps = false;
+                for cells in &mut grid[row .. height.min(row + rowspan)] {
+                    if cells.len() < column + colspan {
+                        cells.resize(column + colspan, false);
+                    }
+                    for cell in &mut cells[column .. column + colspan] {
+                        overlaps |= *cell;
+                        *cell = true;
+                    }
+                }
+                if overlaps {
+                    errors.push(TableError::Overlap { row, column });
+                }
+                column += colspan;
+            }
+            columns.push(row_columns);
+        }
+
+        let width =
+            |cells: &Vec<bool>| cells.iter().filter(|&&cell| cell).count();
+        if let Some(expected) = grid.first().map(width) {
+            for (row, cells) in grid.iter().enumerate() {
+                if width(cells) != expected {
+                    errors.push(TableError::Ragged {
+                        row,
+                        width: width(cells),
+                        expected,
+                    });
+                }
+            }
+        }
+
+        (columns, errors)
+    }
+}
+
+impl<T, E> Table<T, E>
+where
+    T: Component<Kind = InlineComponent>,
+    E: Component,
+{
+    /// Renders the table with the given alignment of the columns, attributes
+    /// of the `<table>` element attached by [`Styled`](super::style::Styled)
+    /// and options written as attributes as well.
+    fn render(
+        &self,
+        fmt: &mut fmt::Formatter,
+        ctx: Context,
+        align: &[Align],
+        attrs: &Attributes,
+        options: &str,
+    ) -> fmt::Result {
         write!(
             fmt,
-            "<div class=\"table-wrapper\"><span \
-             class=\"table-title\">{title}</span><table class=\"table\">",
-            title = ctx.renderer(&self.title),
+            "<div class=\"table-wrapper\"><table{}{}",
+            attrs.display(ctx, "table"),
+            options
         )?;
+        write!(
+            fmt,
+            "><caption class=\"table-title\">{}</caption>",
+            ctx.renderer(&self.title)
+        )?;
+
+        let header_rows = self.header_rows();
+        let (columns, errors) = self.layout();
+        if !errors.is_empty() {
+            let errors =
+                errors.iter().map(ToString::to_string).collect::<Vec<_>>();
+            Err(ctx.report(anyhow!("Invalid table: {}", errors.join("; "))))?;
+        }
+        let rows = self.entries.iter().zip(&columns).enumerate();
+        for (i, (row, row_columns)) in rows {
+            let in_head = i < header_rows;
+            if i == 0 && in_head {
+                write!(fmt, "<thead>")?;
+            } else if i == header_rows {
+                write!(fmt, "<tbody>")?;
+            }
 
-        for row in &self.entries {
             write!(fmt, "<tr>")?;
-            for entry in row {
+            for (entry, &column) in row.iter().zip(row_columns) {
                 write!(fmt, "<{}", entry.tag())?;
+                if entry.header {
+                    let scope = if in_head { "col" } else { "row" };
+                    write!(fmt, " scope=\"{}\"", scope)?;
+                }
                 if entry.rowspan != 1 {
                     write!(fmt, " rowspan=\"{}\"", entry.rowspan)?;
                 }
                 if entry.colspan != 1 {
                     write!(fmt, " colspan=\"{}\"", entry.colspan)?;
                 }
+                if let Some(align) =
+                    align.get(column).and_then(|align| align.as_css())
+                {
+                    write!(fmt, " style=\"text-align: {}\"", align)?;
+                }
                 write!(
                     fmt,
                     ">{}</{}>",
@@ -59,6 +232,13 @@ where
                 )?;
             }
             write!(fmt, "</tr>")?;
+
+            if i + 1 == header_rows {
+                write!(fmt, "</thead>")?;
+            }
+        }
+        if header_rows < self.entries.len() {
+            write!(fmt, "</tbody>")?;
         }
 
         write!(fmt, "</table></div>")?;
@@ -67,6 +247,239 @@ where
     }
 }
 
+impl<T, E> Component for Table<T, E>
+where
+    T: Component<Kind = InlineComponent>,
+    E: Component,
+{
+    type Kind = BlockComponent;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        self.render(fmt, ctx, &[], &Attributes::default(), "")
+    }
+
+    fn to_html_with(
+        &self,
+        fmt: &mut fmt::Formatter,
+        ctx: Context,
+        attrs: &Attributes,
+        _wrapper: &str,
+    ) -> fmt::Result {
+        self.render(fmt, ctx, &[], attrs, "")
+    }
+}
+
+/// A [`Table`] with options on how it is displayed, created by the setters of
+/// the table.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TableOptions<T, E>
+where
+    T: Component<Kind = InlineComponent>,
+    E: Component,
+{
+    /// The table displayed.
+    pub table: Table<T, E>,
+    /// Alignment of the contents of each column. Missing columns are not
+    /// aligned.
+    pub align: Vec<Align>,
+    /// Whether the columns can be sorted by the reader, with a script.
+    pub sortable: bool,
+    /// Whether rows can be filtered by text by the reader, with a script.
+    pub filterable: bool,
+    /// Placeholder of the text box filtering the rows.
+    pub filter_placeholder: String,
+    /// Accessible label of the text box filtering the rows.
+    pub filter_label: String,
+    /// Letters of a custom alphabet, in order, used to sort the columns. The
+    /// collation of the language of the page is used if empty.
+    pub alphabet: Vec<String>,
+}
+
+impl<T, E> TableOptions<T, E>
+where
+    T: Component<Kind = InlineComponent>,
+    E: Component,
+{
+    /// Displays the given table without any option.
+    pub fn new(table: Table<T, E>) -> Self {
+        Self {
+            table,
+            align: Vec::new(),
+            sortable: false,
+            filterable: false,
+            filter_placeholder: String::from("Filter"),
+            filter_label: String::from("Filter table"),
+            alphabet: Vec::new(),
+        }
+    }
+
+    /// Sets the alignment of the contents of a column, counting from zero.
+    pub fn align(mut self, column: usize, align: Align) -> Self {
+        if self.align.len() <= column {
+            self.align.resize(column + 1, Align::Auto);
+        }
+        self.align[column] = align;
+        self
+    }
+
+    /// Makes the columns sortable by the reader. The table must have a single
+    /// header row and no entry spanning several rows or columns.
+    pub fn sortable(mut self) -> Self {
+        self.sortable = true;
+        self
+    }
+
+    /// Adds a text box filtering the rows.
+    pub fn filterable(mut self) -> Self {
+        self.filterable = true;
+        self
+    }
+
+    /// Sets the placeholder of the text box filtering the rows, "Filter" by
+    /// default.
+    pub fn filter_placeholder<S>(mut self, placeholder: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.filter_placeholder = placeholder.into();
+        self
+    }
+
+    /// Sets the accessible label of the text box filtering the rows, "Filter
+    /// table" by default.
+    pub fn filter_label<S>(mut self, label: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.filter_label = label.into();
+        self
+    }
+
+    /// Sorts the columns with a custom alphabet, whose letters may have
+    /// more than one character, such as "ch".
+    pub fn alphabet<I, S>(mut self, letters: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.alphabet = letters.into_iter().map(Into::into).collect();
+        self
+    }
+}
+
+impl<T, E> Component for TableOptions<T, E>
+where
+    T: Component<Kind = InlineComponent>,
+    E: Component,
+{
+    type Kind = BlockComponent;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        self.to_html_with(fmt, ctx, &Attributes::default(), "div")
+    }
+
+    fn to_html_with(
+        &self,
+        fmt: &mut fmt::Formatter,
+        ctx: Context,
+        extra: &Attributes,
+        _wrapper: &str,
+    ) -> fmt::Result {
+        let mut attrs = String::new();
+        if let Some(reason) =
+            self.table.unsortable_reason().filter(|_| self.sortable)
+        {
+            Err(ctx.report(anyhow!("Table cannot be sortable: {}", reason)))?;
+        }
+        if self.sortable || self.filterable {
+            ctx.require_script("js/tables.js", TABLES_SCRIPT);
+        }
+        if self.sortable {
+            attrs.push_str(" data-sortable");
+            if !self.alphabet.is_empty() {
+                attrs.push_str(&format!(
+                    " data-alphabet=\"{}\"",
+                    ctx.renderer(self.alphabet.join(" "))
+                ));
+            }
+        }
+        if self.filterable {
+            attrs.push_str(&format!(
+                " data-filterable data-filter-placeholder=\"{}\" \
+                 data-filter-label=\"{}\"",
+                ctx.renderer(&self.filter_placeholder),
+                ctx.renderer(&self.filter_label)
+            ));
+        }
+        self.table.render(fmt, ctx, &self.align, extra, &attrs)
+    }
+}
+
+/// An error in the grid of a table, found by [`Table::validate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Error)]
+pub enum TableError {
+    /// An entry spans zero rows or columns.
+    #[error("Entry at row {row}, column {column} has a zero span")]
+    ZeroSpan {
+        /// Row of the entry.
+        row: usize,
+        /// Column of the entry.
+        column: usize,
+    },
+    /// An entry overlaps an entry spanning from an upper row.
+    #[error("Entry at row {row}, column {column} overlaps another entry")]
+    Overlap {
+        /// Row of the entry.
+        row: usize,
+        /// Column of the entry.
+        column: usize,
+    },
+    /// An entry spans past the last row of the table.
+    #[error("Entry at row {row}, column {column} spans past the last row")]
+    PastEnd {
+        /// Row of the entry.
+        row: usize,
+        /// Column of the entry.
+        column: usize,
+    },
+    /// A row does not have the same width as the first one.
+    #[error("Row {row} has {width} columns, but the first row has {expected}")]
+    Ragged {
+        /// The row.
+        row: usize,
+        /// Number of columns occupied in the row.
+        width: usize,
+        /// Number of columns occupied in the first row.
+        expected: usize,
+    },
+}
+
+/// Alignment of the contents of a table column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum Align {
+    /// Not aligned, i.e. the default alignment.
+    #[default]
+    Auto,
+    /// Aligned to the left.
+    Left,
+    /// Centered.
+    Center,
+    /// Aligned to the right, such as for numbers.
+    Right,
+}
+
+impl Align {
+    /// The value of the CSS `text-align` property. `None` if not aligned.
+    pub fn as_css(self) -> Option<&'static str> {
+        match self {
+            Align::Auto => None,
+            Align::Left => Some("left"),
+            Align::Center => Some("center"),
+            Align::Right => Some("right"),
+        }
+    }
+}
+
 /// An entry in the table.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Entry<T>
@@ -117,3 +530,120 @@ where
         Self::new(T::default())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{Align, Entry, Table, TableError};
+    use crate::component::TestContext;
+    use std::fmt::Write;
+
+    #[test]
+    fn header_and_scopes() {
+        let test = TestContext::new("langs/index.html");
+        let ctx = test.ctx();
+        let header = |data| Entry { header: true, ..Entry::new(data) };
+        let table = Table::new(
+            "Verbs",
+            vec![
+                vec![header(""), header("Singular"), header("Plural")],
+                vec![
+                    header("1st"),
+                    Entry { rowspan: 2, ..Entry::new("a") },
+                    Entry::new("b"),
+                ],
+                vec![header("2nd"), Entry::new("c")],
+            ],
+        )
+        .align(2, Align::Right);
+        assert_eq!(
+            ctx.renderer(&table).to_string(),
+            "<div class=\"table-wrapper\"><table class=\"table\"><caption \
+             class=\"table-title\">Verbs</caption><thead><tr><th \
+             scope=\"col\"></th><th scope=\"col\">Singular</th><th \
+             scope=\"col\" style=\"text-align: \
+             right\">Plural</th></tr></thead><tbody><tr><th \
+             scope=\"row\">1st</th><td rowspan=\"2\">a</td><td \
+             style=\"text-align: right\">b</td></tr><tr><th \
+             scope=\"row\">2nd</th><td style=\"text-align: \
+             right\">c</td></tr></tbody></table></div>"
+        );
+    }
+
+    #[test]
+    fn validate_grid() {
+        let table = Table::new(
+            "Broken",
+            vec![
+                vec![Entry::new("a"), Entry { rowspan: 2, ..Entry::new("b") }],
+                vec![Entry { colspan: 2, ..Entry::new("c") }],
+                vec![Entry::new("d"), Entry { colspan: 0, ..Entry::new("e") }],
+            ],
+        );
+        assert_eq!(
+            table.validate(),
+            Err(vec![
+                TableError::Overlap { row: 1, column: 0 },
+                TableError::ZeroSpan { row: 2, column: 1 },
+            ])
+        );
+
+        let table = Table::new(
+            "Ragged",
+            vec![
+                vec![Entry::new("a"), Entry { rowspan: 3, ..Entry::new("b") }],
+                vec![Entry::new("c"), Entry::new("d")],
+            ],
+        );
+        assert_eq!(
+            table.validate(),
+            Err(vec![
+                TableError::PastEnd { row: 0, column: 1 },
+                TableError::Ragged { row: 1, width: 3, expected: 2 },
+            ])
+        );
+    }
+
+    #[test]
+    fn sortable_requires_script() {
+        let test = TestContext::new("langs/index.html");
+        let ctx = test.ctx();
+        let table =
+            Table { title: "Plain", entries: vec![vec![Entry::new("a")]] };
+        ctx.renderer(&table).to_string();
+        assert!(test.state.scripts().is_empty());
+
+        let header = |data| Entry { header: true, ..Entry::new(data) };
+        let table = Table::new("Words", vec![vec![header("Word")]])
+            .sortable()
+            .filterable()
+            .filter_placeholder("Filtrar")
+            .filter_label("Filtrar tabela")
+            .alphabet(["a", "c", "ch", "h"]);
+        assert!(ctx.renderer(&table).to_string().starts_with(
+            "<div class=\"table-wrapper\"><table class=\"table\" \
+             data-sortable data-alphabet=\"a c ch h\" data-filterable \
+             data-filter-placeholder=\"Filtrar\" data-filter-label=\"Filtrar \
+             tabela\">"
+        ));
+        assert!(test.state.scripts().contains_key("js/tables.js"));
+
+        let table = Table::new(
+            "Spans",
+            vec![
+                vec![header("Word"), header("Gloss")],
+                vec![
+                    Entry { rowspan: 2, ..Entry::new("cha") },
+                    Entry::new("a"),
+                ],
+                vec![Entry::new("b")],
+            ],
+        )
+        .sortable();
+        let mut html = String::new();
+        assert!(write!(html, "{}", ctx.renderer(&table)).is_err());
+        assert_eq!(
+            test.state.take_error().unwrap().to_string(),
+            "Table cannot be sortable: entries span several rows or columns"
+        );
+    }
+}
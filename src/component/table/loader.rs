@@ -0,0 +1,411 @@
+//! This module loads tables from CSV and TSV files, such as spreadsheets
+//! exported with lexicon or paradigm data.
+//!
+//! Cells may start with a span marker: `{c2}` spans two columns, `{r3}` spans
+//! three rows and `{r2c3}` spans both. Cells covered by a span must be left
+//! empty. A cell starting with a literal brace is written `{{` or `\{`. Inside
+//! cells, `**bold**`, `*italic*`, `` `code` `` and `[text](location)` are
+//! recognized, and a backslash escapes the next character.
+
+use crate::{
+    component::{
+        table::{Entry, Table},
+        text::{Bold, Code, Italic, Link},
+        BlockComponent,
+        Component,
+        Context,
+        DynComponent,
+        InlineComponent,
+    },
+    location::{InternalLoc, InternalPath, Location},
+};
+use anyhow::{anyhow, Context as _};
+use std::{
+    fmt,
+    fs,
+    path::{Path, PathBuf},
+};
+use url::Url;
+
+/// A table loaded from a CSV/TSV file.
+pub type LoadedTable = Table<String, DynComponent<InlineComponent>>;
+
+/// Options to turn CSV or TSV data into a table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TableLoader {
+    /// The byte separating cells, such as `b','`.
+    pub delimiter: u8,
+    /// How many leading rows are made of header entries.
+    pub header_rows: usize,
+    /// How many leading columns are made of header entries.
+    pub header_columns: usize,
+}
+
+impl TableLoader {
+    /// Loader of comma-separated values, without headers.
+    pub fn csv() -> Self {
+        Self { delimiter: b',', header_rows: 0, header_columns: 0 }
+    }
+
+    /// Loader of tab-separated values, without headers.
+    pub fn tsv() -> Self {
+        Self { delimiter: b'\t', ..Self::csv() }
+    }
+
+    /// Sets how many leading rows are made of header entries.
+    pub fn header_rows(mut self, rows: usize) -> Self {
+        self.header_rows = rows;
+        self
+    }
+
+    /// Sets how many leading columns are made of header entries.
+    pub fn header_columns(mut self, columns: usize) -> Self {
+        self.header_columns = columns;
+        self
+    }
+
+    /// Reads a file and turns it into a table with the given title.
+    pub fn load<S, P>(&self, title: S, path: P) -> anyhow::Result<LoadedTable>
+    where
+        S: Into<String>,
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let source = fs::read_to_string(path)
+            .with_context(|| format!("Reading table {}", path.display()))?;
+        self.parse(title, &source)
+            .with_context(|| format!("Parsing table {}", path.display()))
+    }
+
+    /// Turns CSV/TSV source into a table with the given title.
+    pub fn parse<S>(
+        &self,
+        title: S,
+        source: &str,
+    ) -> anyhow::Result<LoadedTable>
+    where
+        S: Into<String>,
+    {
+        let mut reader = csv::ReaderBuilder::new()
+            .delimiter(self.delimiter)
+            .has_headers(false)
+            .flexible(true)
+            .from_reader(source.as_bytes());
+
+        // Rows still covered by a span from an upper row, for each column.
+        let mut covered = Vec::<u32>::new();
+        let mut entries = Vec::new();
+
+        for (row, record) in reader.records().enumerate() {
+            let record = record?;
+            let mut row_entries = Vec::new();
+            // Columns covered by a span from the left in this row.
+            let mut covered_until = 0;
+
+            for (column, cell) in record.iter().enumerate() {
+                let is_covered = column < covered_until
+                    || covered.get(column).is_some_and(|&rows| rows > 0);
+                if is_covered {
+                    if !cell.trim().is_empty() {
+                        Err(anyhow!(
+                            "Cell at row {}, column {} is covered by a span \
+                             but not empty",
+                            row,
+                            column
+                        ))?;
+                    }
+                    continue;
+                }
+
+                let (rowspan, colspan, text) =
+                    parse_spans(cell).with_context(|| {
+                        format!("Cell at row {}, column {}", row, column)
+                    })?;
+                let end = column + colspan as usize;
+                if covered.len() < end {
+                    covered.resize(end, 0);
+                }
+                for rows in &mut covered[column .. end] {
+                    *rows = rowspan;
+                }
+                covered_until = end;
+
+                row_entries.push(Entry {
+                    rowspan,
+                    colspan,
+                    header: row < self.header_rows
+                        || column < self.header_columns,
+                    data: parse_inline(text).with_context(|| {
+                        format!("Cell at row {}, column {}", row, column)
+                    })?,
+                });
+            }
+
+            for rows in &mut covered {
+                *rows = rows.saturating_sub(1);
+            }
+            entries.push(row_entries);
+        }
+
+        Ok(Table::new(title.into(), entries))
+    }
+}
+
+impl Default for TableLoader {
+    fn default() -> Self {
+        Self::csv()
+    }
+}
+
+/// Where the file of a [`TableFile`] is.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TablePath {
+    /// A file of the assets, which must be given to the rendering.
+    Asset(InternalPath),
+    /// A file anywhere in the filesystem, such as in the content directory.
+    Fs(PathBuf),
+}
+
+impl From<InternalPath> for TablePath {
+    fn from(path: InternalPath) -> Self {
+        TablePath::Asset(path)
+    }
+}
+
+impl From<PathBuf> for TablePath {
+    fn from(path: PathBuf) -> Self {
+        TablePath::Fs(path)
+    }
+}
+
+impl From<&Path> for TablePath {
+    fn from(path: &Path) -> Self {
+        TablePath::Fs(path.to_owned())
+    }
+}
+
+/// A table loaded from a file when the page is generated.
+#[derive(Debug, Clone)]
+pub struct TableFile {
+    /// The title of the table.
+    pub title: String,
+    /// Path to the file.
+    pub path: TablePath,
+    /// How the file is turned into a table.
+    pub loader: TableLoader,
+}
+
+impl TableFile {
+    /// Creates a table loaded from the given file, either a path of the
+    /// assets or a filesystem path.
+    pub fn new<S, P>(title: S, path: P, loader: TableLoader) -> Self
+    where
+        S: Into<String>,
+        P: Into<TablePath>,
+    {
+        Self { title: title.into(), path: path.into(), loader }
+    }
+}
+
+impl Component for TableFile {
+    type Kind = BlockComponent;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        let fs_path = match &self.path {
+            TablePath::Asset(path) => ctx
+                .assets()
+                .ok_or_else(|| {
+                    ctx.report(anyhow!(
+                        "Table file {} needs the assets directory",
+                        path
+                    ))
+                })?
+                .fs_path(path),
+            TablePath::Fs(path) => path.clone(),
+        };
+        let table = self
+            .loader
+            .load(self.title.clone(), fs_path)
+            .map_err(|error| ctx.report(error))?;
+        table.to_html(fmt, ctx)
+    }
+}
+
+/// Splits a leading span marker such as `{r2c3}` from the text of a cell.
+/// Returns the rowspan, the colspan and the remaining text.
+fn parse_spans(cell: &str) -> anyhow::Result<(u32, u32, &str)> {
+    let trimmed = cell.trim_start();
+    if trimmed.starts_with("{{") {
+        return Ok((1, 1, &trimmed[1 ..]));
+    }
+    let marker = trimmed
+        .strip_prefix('{')
+        .and_then(|rest| rest.split_once('}'))
+        .map(|(marker, rest)| (marker, rest.trim_start()));
+    let (marker, text) = match marker {
+        Some(marker) => marker,
+        None => return Ok((1, 1, cell)),
+    };
+
+    let (mut rowspan, mut colspan) = (1, 1);
+    let mut rest = marker;
+    while let Some(kind) = rest.chars().next() {
+        if !kind.is_ascii() {
+            Err(anyhow!("Invalid span marker {{{}}}", marker))?;
+        }
+        let digits = rest[1 ..]
+            .find(|ch: char| !ch.is_ascii_digit())
+            .map_or(rest.len(), |end| end + 1);
+        let count = rest[1 .. digits]
+            .parse::<u32>()
+            .map_err(|_| anyhow!("Invalid span marker {{{}}}", marker))?;
+        match kind {
+            'r' => rowspan = count,
+            'c' => colspan = count,
+            _ => Err(anyhow!("Invalid span marker {{{}}}", marker))?,
+        }
+        rest = &rest[digits ..];
+    }
+    if marker.is_empty() || rowspan == 0 || colspan == 0 {
+        Err(anyhow!("Invalid span marker {{{}}}", marker))?;
+    }
+
+    Ok((rowspan, colspan, text))
+}
+
+/// Parses the inline markup of a cell.
+fn parse_inline(text: &str) -> anyhow::Result<DynComponent<InlineComponent>> {
+    let mut parts = Vec::<DynComponent<InlineComponent>>::new();
+    let mut plain = String::new();
+    let mut rest = text;
+
+    while let Some(ch) = rest.chars().next() {
+        let delimited = |open: &str, close: &str| {
+            let inner = rest.strip_prefix(open)?;
+            let end = inner.find(close)?;
+            Some((&inner[.. end], &inner[end + close.len() ..]))
+        };
+
+        let (part, after) = if ch == '\\' {
+            let escaped = rest[1 ..].chars().next().map_or(0, char::len_utf8);
+            plain.push_str(&rest[1 .. 1 + escaped]);
+            rest = &rest[1 + escaped ..];
+            continue;
+        } else if let Some((inner, after)) = delimited("**", "**") {
+            (Bold(unescape(inner)).to_dyn(), after)
+        } else if let Some((inner, after)) = delimited("*", "*") {
+            (Italic(unescape(inner)).to_dyn(), after)
+        } else if let Some((inner, after)) = delimited("`", "`") {
+            (Code(inner.to_owned()).to_dyn(), after)
+        } else if let Some((inner, after)) = delimited("[", ")") {
+            match inner.split_once("](") {
+                Some((text, location)) => (
+                    Link {
+                        text: unescape(text),
+                        location: parse_location(location)?,
+                    }
+                    .to_dyn(),
+                    after,
+                ),
+                None => {
+                    plain.push(ch);
+                    rest = &rest[ch.len_utf8() ..];
+                    continue;
+                },
+            }
+        } else {
+            plain.push(ch);
+            rest = &rest[ch.len_utf8() ..];
+            continue;
+        };
+
+        if !plain.is_empty() {
+            parts.push(std::mem::take(&mut plain).to_dyn());
+        }
+        parts.push(part);
+        rest = after;
+    }
+
+    if !plain.is_empty() {
+        parts.push(plain.to_dyn());
+    }
+    Ok(match parts.len() {
+        1 => parts.pop().unwrap(),
+        _ => parts.to_dyn(),
+    })
+}
+
+/// Removes the backslashes escaping characters.
+fn unescape(text: &str) -> String {
+    let mut string = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' => string.extend(chars.next()),
+            _ => string.push(ch),
+        }
+    }
+    string
+}
+
+/// Parses the location of a link: either an URL with a scheme or an internal
+/// location.
+fn parse_location(location: &str) -> anyhow::Result<Location> {
+    if location.contains("://") || location.starts_with("mailto:") {
+        Ok(Location::Url(Url::parse(location)?))
+    } else {
+        Ok(Location::Internal(InternalLoc::parse(location)?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{TableFile, TableLoader};
+    use crate::component::TestContext;
+    use std::fs;
+
+    #[test]
+    fn spans_and_markup() {
+        let test = TestContext::new("langs/index.html");
+        let ctx = test.ctx();
+        let table = TableLoader::tsv()
+            .header_rows(1)
+            .header_columns(1)
+            .parse(
+                "Nouns",
+                concat!(
+                    "\t{c2}Number\t\n",
+                    "Nominative\t*lupus*\t**lupi**\n",
+                    "Meaning\t{c2}[wolf](langs/lat#wolf)\t\n",
+                ),
+            )
+            .unwrap();
+        assert_eq!(table.validate(), Ok(()));
+        assert_eq!(
+            ctx.renderer(&table).to_string(),
+            "<div class=\"table-wrapper\"><table class=\"table\"><caption \
+             class=\"table-title\">Nouns</caption><thead><tr><th \
+             scope=\"col\"></th><th scope=\"col\" \
+             colspan=\"2\">Number</th></tr></thead><tbody><tr><th \
+             scope=\"row\">Nominative</th><td><i \
+             class=\"italic\">lupus</i></td><td><b \
+             class=\"bold\">lupi</b></td></tr><tr><th \
+             scope=\"row\">Meaning</th><td colspan=\"2\"><a \
+             href=\"../langs/lat#wolf\" \
+             class=\"link\">wolf</a></td></tr></tbody></table></div>"
+        );
+
+        assert!(TableLoader::csv().parse("Bad", "{c2}a,b").is_err());
+        let error = TableLoader::csv().parse("Empty", "{}a,b").unwrap_err();
+        assert!(format!("{:#}", error).ends_with("Invalid span marker {}"));
+
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("sets.csv");
+        fs::write(&path, "{{a},\\{b},`{c}`\n").unwrap();
+        let table = TableFile::new("Sets", path, TableLoader::csv());
+        assert!(ctx.renderer(&table).to_string().contains(
+            "<tr><td>{a}</td><td>{b}</td><td><code \
+             class=\"code\">{c}</code></td></tr>"
+        ));
+    }
+}
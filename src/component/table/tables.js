@@ -0,0 +1,141 @@
+// Sortable and filterable tables. Tables opt in with the `data-sortable` and
+// `data-filterable` attributes; `data-alphabet` lists the letters of a custom
+// alphabet in order, separated by spaces.
+(function () {
+    "use strict";
+
+    function compareKeys(left, right) {
+        for (var i = 0; i < left.length && i < right.length; i++) {
+            if (left[i] !== right[i]) {
+                return left[i] - right[i];
+            }
+        }
+        return left.length - right.length;
+    }
+
+    function comparator(table) {
+        var langElement = table.closest("[lang]");
+        var collator = new Intl.Collator(
+            langElement ? langElement.lang : undefined,
+            { numeric: true, sensitivity: "base" }
+        );
+        var alphabet = (table.dataset.alphabet || "")
+            .split(" ")
+            .filter(function (letter) { return letter.length > 0; });
+        if (alphabet.length === 0) {
+            return collator.compare;
+        }
+
+        var order = {};
+        alphabet.forEach(function (letter, i) {
+            order[letter.toLowerCase()] = i;
+        });
+        // Longest letters first, so that digraphs win over their parts.
+        var letters = Object.keys(order).sort(function (left, right) {
+            return right.length - left.length;
+        });
+
+        function key(text) {
+            var keys = [];
+            var i = 0;
+            text = text.toLowerCase();
+            while (i < text.length) {
+                var letter = null;
+                for (var j = 0; j < letters.length; j++) {
+                    if (text.startsWith(letters[j], i)) {
+                        letter = letters[j];
+                        break;
+                    }
+                }
+                if (letter !== null) {
+                    keys.push(order[letter]);
+                    i += letter.length;
+                } else {
+                    var code = text.codePointAt(i);
+                    keys.push(alphabet.length + code);
+                    i += code > 0xffff ? 2 : 1;
+                }
+            }
+            return keys;
+        }
+
+        return function (left, right) {
+            return compareKeys(key(left), key(right))
+                || collator.compare(left, right);
+        };
+    }
+
+    function makeSortable(table) {
+        var head = table.tHead;
+        var body = table.tBodies[0];
+        if (!head || !body || head.rows.length === 0) {
+            return;
+        }
+        var compare = comparator(table);
+        var headers = head.rows[head.rows.length - 1].cells;
+
+        Array.prototype.forEach.call(headers, function (header, column) {
+            var button = document.createElement("button");
+            button.type = "button";
+            button.className = "table-sort";
+            while (header.firstChild) {
+                button.appendChild(header.firstChild);
+            }
+            header.appendChild(button);
+
+            button.addEventListener("click", function () {
+                var ascending = header.getAttribute("aria-sort") !== "ascending";
+                Array.prototype.forEach.call(headers, function (other) {
+                    other.removeAttribute("aria-sort");
+                });
+                header.setAttribute(
+                    "aria-sort",
+                    ascending ? "ascending" : "descending"
+                );
+
+                var rows = Array.prototype.slice.call(body.rows);
+                rows.sort(function (left, right) {
+                    var leftCell = left.cells[column];
+                    var rightCell = right.cells[column];
+                    var order = compare(
+                        leftCell ? leftCell.textContent.trim() : "",
+                        rightCell ? rightCell.textContent.trim() : ""
+                    );
+                    return ascending ? order : -order;
+                });
+                rows.forEach(function (row) {
+                    body.appendChild(row);
+                });
+            });
+        });
+    }
+
+    function makeFilterable(table) {
+        var input = document.createElement("input");
+        input.type = "search";
+        input.className = "table-filter";
+        input.placeholder = table.getAttribute("data-filter-placeholder");
+        input.setAttribute(
+            "aria-label",
+            table.getAttribute("data-filter-label")
+        );
+        table.parentNode.insertBefore(input, table);
+
+        input.addEventListener("input", function () {
+            var query = input.value.trim().toLocaleLowerCase();
+            Array.prototype.forEach.call(table.tBodies, function (body) {
+                Array.prototype.forEach.call(body.rows, function (row) {
+                    var text = row.textContent.toLocaleLowerCase();
+                    row.hidden = query.length > 0 && text.indexOf(query) < 0;
+                });
+            });
+        });
+    }
+
+    document
+        .querySelectorAll("table[data-sortable]")
+        .forEach(makeSortable);
+    document
+        .querySelectorAll("table[data-filterable]")
+        .forEach(makeFilterable);
+})();
@@ -0,0 +1,133 @@
+//! This module exports the component that uses a template of the site's
+//! [`Templates`](crate::template::Templates).
+
+use crate::{
+    component::{BlockComponent, Component, Context, InlineComponent},
+    template::TemplateArgs,
+};
+use std::fmt;
+
+/// A use of a named template of the site, with arguments. The template is
+/// resolved when rendering; unknown templates and wrong arguments make the
+/// generation of the page fail.
+#[derive(Debug, Clone)]
+pub struct Transclude {
+    /// Name of the template.
+    pub name: String,
+    /// Arguments given to the template.
+    pub args: TemplateArgs,
+}
+
+impl Transclude {
+    /// Uses the given template, without arguments yet.
+    pub fn new<S>(name: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self { name: name.into(), args: TemplateArgs::new() }
+    }
+
+    /// Gives an argument to the template.
+    pub fn arg<S, T>(mut self, param: S, value: T) -> Self
+    where
+        S: Into<String>,
+        T: Component<Kind = InlineComponent> + Send + Sync + 'static,
+    {
+        self.args = self.args.arg(param, value);
+        self
+    }
+}
+
+impl Component for Transclude {
+    type Kind = BlockComponent;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        let contents = ctx
+            .site()
+            .templates
+            .instantiate(&self.name, &self.args)
+            .map_err(|error| ctx.report(error))?;
+        ctx.state
+            .enter_template(&self.name)
+            .map_err(|error| ctx.report(error))?;
+        let result = write!(fmt, "{}", ctx.renderer(contents));
+        ctx.state.leave_template();
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Transclude;
+    use crate::{
+        component::{text::Paragraph, Component},
+        location::InternalPath,
+        page::{Page, RenderPage},
+        site::Site,
+        template::Template,
+    };
+
+    #[test]
+    fn navbox() {
+        let mut site = Site::default();
+        site.templates.insert(
+            "navbox",
+            Template::new(|args| {
+                Paragraph(args.get("title").cloned()).to_dyn()
+            })
+            .param("title"),
+        );
+        let location = InternalPath::parse("langs/index.html").unwrap();
+        let page = Page {
+            title: String::from("Languages"),
+            body: Transclude::new("navbox").arg("title", "Northern").to_dyn(),
+            sections: Vec::new(),
+            lang: None,
+        };
+        let render =
+            RenderPage { page: &page, location: &location, site: &site };
+        let mut html = Vec::new();
+        render.write_to(&mut html).unwrap();
+        assert!(String::from_utf8(html)
+            .unwrap()
+            .contains("<p class=\"paragraph\">Northern</p>"));
+
+        let page = Page {
+            title: String::from("Languages"),
+            body: Transclude::new("navbox").arg("name", "Northern").to_dyn(),
+            sections: Vec::new(),
+            lang: None,
+        };
+        let error =
+            RenderPage { page: &page, location: &location, site: &site }
+                .write_to(Vec::new())
+                .unwrap_err();
+        assert_eq!(
+            error.root_cause().to_string(),
+            "Unknown parameter name of template navbox"
+        );
+
+        site.templates.insert(
+            "north",
+            Template::new(|_| Transclude::new("south").to_dyn()),
+        );
+        site.templates.insert(
+            "south",
+            Template::new(|_| Transclude::new("north").to_dyn()),
+        );
+        let page = Page {
+            title: String::from("Languages"),
+            body: Transclude::new("north").to_dyn(),
+            sections: Vec::new(),
+            lang: None,
+        };
+        let error =
+            RenderPage { page: &page, location: &location, site: &site }
+                .write_to(Vec::new())
+                .unwrap_err();
+        assert_eq!(
+            error.root_cause().to_string(),
+            "Template north transcludes itself: north -> south -> north"
+        );
+    }
+}
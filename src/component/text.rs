@@ -1,7 +1,13 @@
 //! This module exports components more related to text.
 
 use crate::{
-    component::{BlockComponent, Component, Context, InlineComponent},
+    component::{
+        style::Attributes,
+        BlockComponent,
+        Component,
+        Context,
+        InlineComponent,
+    },
     location::Location,
 };
 use std::fmt;
@@ -19,7 +25,22 @@ where
     type Kind = T::Kind;
 
     fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
-        write!(fmt, "<b class=\"bold\">{}</b>", ctx.renderer(&self.0))?;
+        self.to_html_with(fmt, ctx, &Attributes::default(), "span")
+    }
+
+    fn to_html_with(
+        &self,
+        fmt: &mut fmt::Formatter,
+        ctx: Context,
+        attrs: &Attributes,
+        _wrapper: &str,
+    ) -> fmt::Result {
+        write!(
+            fmt,
+            "<b{}>{}</b>",
+            attrs.display(ctx, "bold"),
+            ctx.renderer(&self.0)
+        )?;
         Ok(())
     }
 }
@@ -31,7 +52,22 @@ where
     type Kind = T::Kind;
 
     fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
-        write!(fmt, "<div class=\"bold\">{}</div>", ctx.renderer(&self.0))?;
+        self.to_html_with(fmt, ctx, &Attributes::default(), "div")
+    }
+
+    fn to_html_with(
+        &self,
+        fmt: &mut fmt::Formatter,
+        ctx: Context,
+        attrs: &Attributes,
+        _wrapper: &str,
+    ) -> fmt::Result {
+        write!(
+            fmt,
+            "<div{}>{}</div>",
+            attrs.display(ctx, "bold"),
+            ctx.renderer(&self.0)
+        )?;
         Ok(())
     }
 }
@@ -49,7 +85,22 @@ where
     type Kind = T::Kind;
 
     fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
-        write!(fmt, "<i class=\"italic\">{}</i>", ctx.renderer(&self.0))?;
+        self.to_html_with(fmt, ctx, &Attributes::default(), "span")
+    }
+
+    fn to_html_with(
+        &self,
+        fmt: &mut fmt::Formatter,
+        ctx: Context,
+        attrs: &Attributes,
+        _wrapper: &str,
+    ) -> fmt::Result {
+        write!(
+            fmt,
+            "<i{}>{}</i>",
+            attrs.display(ctx, "italic"),
+            ctx.renderer(&self.0)
+        )?;
         Ok(())
     }
 }
@@ -61,7 +112,22 @@ where
     type Kind = T::Kind;
 
     fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
-        write!(fmt, "<div class=\"italic\">{}</div>", ctx.renderer(&self.0))?;
+        self.to_html_with(fmt, ctx, &Attributes::default(), "div")
+    }
+
+    fn to_html_with(
+        &self,
+        fmt: &mut fmt::Formatter,
+        ctx: Context,
+        attrs: &Attributes,
+        _wrapper: &str,
+    ) -> fmt::Result {
+        write!(
+            fmt,
+            "<div{}>{}</div>",
+            attrs.display(ctx, "italic"),
+            ctx.renderer(&self.0)
+        )?;
         Ok(())
     }
 }
@@ -80,7 +146,22 @@ where
     type Kind = T::Kind;
 
     fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
-        write!(fmt, "<pre class=\"pre\">{}</pre>", ctx.renderer(&self.0))?;
+        self.to_html_with(fmt, ctx, &Attributes::default(), "span")
+    }
+
+    fn to_html_with(
+        &self,
+        fmt: &mut fmt::Formatter,
+        ctx: Context,
+        attrs: &Attributes,
+        _wrapper: &str,
+    ) -> fmt::Result {
+        write!(
+            fmt,
+            "<pre{}>{}</pre>",
+            attrs.display(ctx, "pre"),
+            ctx.renderer(&self.0)
+        )?;
         Ok(())
     }
 }
@@ -92,7 +173,510 @@ where
     type Kind = T::Kind;
 
     fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
-        write!(fmt, "<div class=\"pre\">{}</div>", ctx.renderer(&self.0))?;
+        self.to_html_with(fmt, ctx, &Attributes::default(), "div")
+    }
+
+    fn to_html_with(
+        &self,
+        fmt: &mut fmt::Formatter,
+        ctx: Context,
+        attrs: &Attributes,
+        _wrapper: &str,
+    ) -> fmt::Result {
+        write!(
+            fmt,
+            "<div{}>{}</div>",
+            attrs.display(ctx, "pre"),
+            ctx.renderer(&self.0)
+        )?;
+        Ok(())
+    }
+}
+
+/// Struck-through text, such as removed or no longer accurate text. The
+/// parameter is wrapped to strike its text through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Strikethrough<T, K>(pub T)
+where
+    T: Component<Kind = K>;
+
+impl<T> Component for Strikethrough<T, InlineComponent>
+where
+    T: Component<Kind = InlineComponent>,
+{
+    type Kind = T::Kind;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        self.to_html_with(fmt, ctx, &Attributes::default(), "span")
+    }
+
+    fn to_html_with(
+        &self,
+        fmt: &mut fmt::Formatter,
+        ctx: Context,
+        attrs: &Attributes,
+        _wrapper: &str,
+    ) -> fmt::Result {
+        write!(
+            fmt,
+            "<s{}>{}</s>",
+            attrs.display(ctx, "strikethrough"),
+            ctx.renderer(&self.0)
+        )?;
+        Ok(())
+    }
+}
+
+impl<T> Component for Strikethrough<T, BlockComponent>
+where
+    T: Component<Kind = BlockComponent>,
+{
+    type Kind = T::Kind;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        self.to_html_with(fmt, ctx, &Attributes::default(), "div")
+    }
+
+    fn to_html_with(
+        &self,
+        fmt: &mut fmt::Formatter,
+        ctx: Context,
+        attrs: &Attributes,
+        _wrapper: &str,
+    ) -> fmt::Result {
+        write!(
+            fmt,
+            "<div{}>{}</div>",
+            attrs.display(ctx, "strikethrough"),
+            ctx.renderer(&self.0)
+        )?;
+        Ok(())
+    }
+}
+
+/// Underlined text. The parameter is wrapped to underline its text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Underline<T, K>(pub T)
+where
+    T: Component<Kind = K>;
+
+impl<T> Component for Underline<T, InlineComponent>
+where
+    T: Component<Kind = InlineComponent>,
+{
+    type Kind = T::Kind;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        self.to_html_with(fmt, ctx, &Attributes::default(), "span")
+    }
+
+    fn to_html_with(
+        &self,
+        fmt: &mut fmt::Formatter,
+        ctx: Context,
+        attrs: &Attributes,
+        _wrapper: &str,
+    ) -> fmt::Result {
+        write!(
+            fmt,
+            "<u{}>{}</u>",
+            attrs.display(ctx, "underline"),
+            ctx.renderer(&self.0)
+        )?;
+        Ok(())
+    }
+}
+
+impl<T> Component for Underline<T, BlockComponent>
+where
+    T: Component<Kind = BlockComponent>,
+{
+    type Kind = T::Kind;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        self.to_html_with(fmt, ctx, &Attributes::default(), "div")
+    }
+
+    fn to_html_with(
+        &self,
+        fmt: &mut fmt::Formatter,
+        ctx: Context,
+        attrs: &Attributes,
+        _wrapper: &str,
+    ) -> fmt::Result {
+        write!(
+            fmt,
+            "<div{}>{}</div>",
+            attrs.display(ctx, "underline"),
+            ctx.renderer(&self.0)
+        )?;
+        Ok(())
+    }
+}
+
+/// Superscript text. The parameter is wrapped to raise its text above the
+/// baseline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Superscript<T, K>(pub T)
+where
+    T: Component<Kind = K>;
+
+impl<T> Component for Superscript<T, InlineComponent>
+where
+    T: Component<Kind = InlineComponent>,
+{
+    type Kind = T::Kind;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        self.to_html_with(fmt, ctx, &Attributes::default(), "span")
+    }
+
+    fn to_html_with(
+        &self,
+        fmt: &mut fmt::Formatter,
+        ctx: Context,
+        attrs: &Attributes,
+        _wrapper: &str,
+    ) -> fmt::Result {
+        write!(
+            fmt,
+            "<sup{}>{}</sup>",
+            attrs.display(ctx, "superscript"),
+            ctx.renderer(&self.0)
+        )?;
+        Ok(())
+    }
+}
+
+impl<T> Component for Superscript<T, BlockComponent>
+where
+    T: Component<Kind = BlockComponent>,
+{
+    type Kind = T::Kind;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        self.to_html_with(fmt, ctx, &Attributes::default(), "div")
+    }
+
+    fn to_html_with(
+        &self,
+        fmt: &mut fmt::Formatter,
+        ctx: Context,
+        attrs: &Attributes,
+        _wrapper: &str,
+    ) -> fmt::Result {
+        write!(
+            fmt,
+            "<div{}>{}</div>",
+            attrs.display(ctx, "superscript"),
+            ctx.renderer(&self.0)
+        )?;
+        Ok(())
+    }
+}
+
+/// Subscript text. The parameter is wrapped to lower its text below the
+/// baseline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Subscript<T, K>(pub T)
+where
+    T: Component<Kind = K>;
+
+impl<T> Component for Subscript<T, InlineComponent>
+where
+    T: Component<Kind = InlineComponent>,
+{
+    type Kind = T::Kind;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        self.to_html_with(fmt, ctx, &Attributes::default(), "span")
+    }
+
+    fn to_html_with(
+        &self,
+        fmt: &mut fmt::Formatter,
+        ctx: Context,
+        attrs: &Attributes,
+        _wrapper: &str,
+    ) -> fmt::Result {
+        write!(
+            fmt,
+            "<sub{}>{}</sub>",
+            attrs.display(ctx, "subscript"),
+            ctx.renderer(&self.0)
+        )?;
+        Ok(())
+    }
+}
+
+impl<T> Component for Subscript<T, BlockComponent>
+where
+    T: Component<Kind = BlockComponent>,
+{
+    type Kind = T::Kind;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        self.to_html_with(fmt, ctx, &Attributes::default(), "div")
+    }
+
+    fn to_html_with(
+        &self,
+        fmt: &mut fmt::Formatter,
+        ctx: Context,
+        attrs: &Attributes,
+        _wrapper: &str,
+    ) -> fmt::Result {
+        write!(
+            fmt,
+            "<div{}>{}</div>",
+            attrs.display(ctx, "subscript"),
+            ctx.renderer(&self.0)
+        )?;
+        Ok(())
+    }
+}
+
+/// Text in small capitals, such as the glosses of grammatical morphemes. The
+/// parameter is wrapped to make its lowercase letters small capitals. Having
+/// no HTML element of its own, it relies on the `small-caps` class of the
+/// site's style sheet, like the classes of the other text styles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct SmallCaps<T, K>(pub T)
+where
+    T: Component<Kind = K>;
+
+impl<T> Component for SmallCaps<T, InlineComponent>
+where
+    T: Component<Kind = InlineComponent>,
+{
+    type Kind = T::Kind;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        self.to_html_with(fmt, ctx, &Attributes::default(), "span")
+    }
+
+    fn to_html_with(
+        &self,
+        fmt: &mut fmt::Formatter,
+        ctx: Context,
+        attrs: &Attributes,
+        _wrapper: &str,
+    ) -> fmt::Result {
+        write!(
+            fmt,
+            "<span{}>{}</span>",
+            attrs.display(ctx, "small-caps"),
+            ctx.renderer(&self.0)
+        )?;
+        Ok(())
+    }
+}
+
+impl<T> Component for SmallCaps<T, BlockComponent>
+where
+    T: Component<Kind = BlockComponent>,
+{
+    type Kind = T::Kind;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        self.to_html_with(fmt, ctx, &Attributes::default(), "div")
+    }
+
+    fn to_html_with(
+        &self,
+        fmt: &mut fmt::Formatter,
+        ctx: Context,
+        attrs: &Attributes,
+        _wrapper: &str,
+    ) -> fmt::Result {
+        write!(
+            fmt,
+            "<div{}>{}</div>",
+            attrs.display(ctx, "small-caps"),
+            ctx.renderer(&self.0)
+        )?;
+        Ok(())
+    }
+}
+
+/// Highlighted text. The parameter is wrapped to mark its text as relevant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Highlight<T, K>(pub T)
+where
+    T: Component<Kind = K>;
+
+impl<T> Component for Highlight<T, InlineComponent>
+where
+    T: Component<Kind = InlineComponent>,
+{
+    type Kind = T::Kind;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        self.to_html_with(fmt, ctx, &Attributes::default(), "span")
+    }
+
+    fn to_html_with(
+        &self,
+        fmt: &mut fmt::Formatter,
+        ctx: Context,
+        attrs: &Attributes,
+        _wrapper: &str,
+    ) -> fmt::Result {
+        write!(
+            fmt,
+            "<mark{}>{}</mark>",
+            attrs.display(ctx, "highlight"),
+            ctx.renderer(&self.0)
+        )?;
+        Ok(())
+    }
+}
+
+impl<T> Component for Highlight<T, BlockComponent>
+where
+    T: Component<Kind = BlockComponent>,
+{
+    type Kind = T::Kind;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        self.to_html_with(fmt, ctx, &Attributes::default(), "div")
+    }
+
+    fn to_html_with(
+        &self,
+        fmt: &mut fmt::Formatter,
+        ctx: Context,
+        attrs: &Attributes,
+        _wrapper: &str,
+    ) -> fmt::Result {
+        write!(
+            fmt,
+            "<div{}>{}</div>",
+            attrs.display(ctx, "highlight"),
+            ctx.renderer(&self.0)
+        )?;
+        Ok(())
+    }
+}
+
+/// Keyboard input. The parameter is wrapped to show its text as keys to be
+/// typed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Keyboard<T, K>(pub T)
+where
+    T: Component<Kind = K>;
+
+impl<T> Component for Keyboard<T, InlineComponent>
+where
+    T: Component<Kind = InlineComponent>,
+{
+    type Kind = T::Kind;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        self.to_html_with(fmt, ctx, &Attributes::default(), "span")
+    }
+
+    fn to_html_with(
+        &self,
+        fmt: &mut fmt::Formatter,
+        ctx: Context,
+        attrs: &Attributes,
+        _wrapper: &str,
+    ) -> fmt::Result {
+        write!(
+            fmt,
+            "<kbd{}>{}</kbd>",
+            attrs.display(ctx, "keyboard"),
+            ctx.renderer(&self.0)
+        )?;
+        Ok(())
+    }
+}
+
+impl<T> Component for Keyboard<T, BlockComponent>
+where
+    T: Component<Kind = BlockComponent>,
+{
+    type Kind = T::Kind;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        self.to_html_with(fmt, ctx, &Attributes::default(), "div")
+    }
+
+    fn to_html_with(
+        &self,
+        fmt: &mut fmt::Formatter,
+        ctx: Context,
+        attrs: &Attributes,
+        _wrapper: &str,
+    ) -> fmt::Result {
+        write!(
+            fmt,
+            "<div{}>{}</div>",
+            attrs.display(ctx, "keyboard"),
+            ctx.renderer(&self.0)
+        )?;
+        Ok(())
+    }
+}
+
+/// Code in the middle of the text. The parameter is wrapped to show its text
+/// as code, without highlighting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Code<T, K>(pub T)
+where
+    T: Component<Kind = K>;
+
+impl<T> Component for Code<T, InlineComponent>
+where
+    T: Component<Kind = InlineComponent>,
+{
+    type Kind = T::Kind;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        self.to_html_with(fmt, ctx, &Attributes::default(), "span")
+    }
+
+    fn to_html_with(
+        &self,
+        fmt: &mut fmt::Formatter,
+        ctx: Context,
+        attrs: &Attributes,
+        _wrapper: &str,
+    ) -> fmt::Result {
+        write!(
+            fmt,
+            "<code{}>{}</code>",
+            attrs.display(ctx, "code"),
+            ctx.renderer(&self.0)
+        )?;
+        Ok(())
+    }
+}
+
+impl<T> Component for Code<T, BlockComponent>
+where
+    T: Component<Kind = BlockComponent>,
+{
+    type Kind = T::Kind;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        self.to_html_with(fmt, ctx, &Attributes::default(), "div")
+    }
+
+    fn to_html_with(
+        &self,
+        fmt: &mut fmt::Formatter,
+        ctx: Context,
+        attrs: &Attributes,
+        _wrapper: &str,
+    ) -> fmt::Result {
+        write!(
+            fmt,
+            "<div{}>{}</div>",
+            attrs.display(ctx, "code"),
+            ctx.renderer(&self.0)
+        )?;
         Ok(())
     }
 }
@@ -110,7 +694,22 @@ where
     type Kind = BlockComponent;
 
     fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
-        write!(fmt, "<p class=\"paragraph\">{}</p>", ctx.renderer(&self.0))?;
+        self.to_html_with(fmt, ctx, &Attributes::default(), "div")
+    }
+
+    fn to_html_with(
+        &self,
+        fmt: &mut fmt::Formatter,
+        ctx: Context,
+        attrs: &Attributes,
+        _wrapper: &str,
+    ) -> fmt::Result {
+        write!(
+            fmt,
+            "<p{}>{}</p>",
+            attrs.display(ctx, "paragraph"),
+            ctx.renderer(&self.0)
+        )?;
         Ok(())
     }
 }
@@ -134,12 +733,52 @@ where
     type Kind = InlineComponent;
 
     fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        self.to_html_with(fmt, ctx, &Attributes::default(), "span")
+    }
+
+    fn to_html_with(
+        &self,
+        fmt: &mut fmt::Formatter,
+        ctx: Context,
+        attrs: &Attributes,
+        _wrapper: &str,
+    ) -> fmt::Result {
         write!(
             fmt,
-            "<a href=\"{}\" class=\"link\">{}</a>",
+            "<a href=\"{}\"{}>{}</a>",
             ctx.renderer(&self.location),
+            attrs.display(ctx, "link"),
             ctx.renderer(&self.text),
         )?;
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{SmallCaps, Subscript, Superscript};
+    use crate::component::{Component, TestContext};
+
+    #[test]
+    fn gloss_notation() {
+        let test = TestContext::new("langs/index.html");
+        let ctx = test.ctx();
+        let gloss = vec![
+            "wolf-".to_dyn(),
+            SmallCaps("nom.sg").to_dyn(),
+            " H".to_dyn(),
+            Subscript("2").to_dyn(),
+            "O".to_dyn(),
+        ];
+        assert_eq!(
+            ctx.renderer(&gloss).to_string(),
+            "wolf-<span class=\"small-caps\">nom.sg</span> H<sub \
+             class=\"subscript\">2</sub>O"
+        );
+        assert!(test.state.styles().is_empty());
+        assert_eq!(
+            ctx.renderer(Superscript("th")).to_string(),
+            "<sup class=\"superscript\">th</sup>"
+        );
+    }
+}
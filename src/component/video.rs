@@ -0,0 +1,220 @@
+//! This module provides a video component for the encyclopedia.
+
+use crate::{
+    component::{
+        media::{write_fallback, write_sources, MediaKind},
+        BlockComponent,
+        Component,
+        Context,
+    },
+    location::Location,
+};
+use std::fmt;
+
+/// The video component. Browsers play the first source they support.
+#[derive(Debug, Clone)]
+pub struct Video {
+    /// Locations of the video files, each in a different format, in order of
+    /// preference.
+    pub sources: Vec<Location>,
+    /// Image shown before the video is played.
+    pub poster: Option<Location>,
+    /// Text tracks, such as subtitles.
+    pub tracks: Vec<Track>,
+    /// Width of the video in pixels.
+    pub width: Option<u32>,
+    /// Height of the video in pixels.
+    pub height: Option<u32>,
+}
+
+impl Video {
+    /// Creates a video with a single source.
+    pub fn new(src: Location) -> Self {
+        Self {
+            sources: vec![src],
+            poster: None,
+            tracks: Vec::new(),
+            width: None,
+            height: None,
+        }
+    }
+
+    /// Appends an alternative source, in a different format.
+    pub fn source(mut self, src: Location) -> Self {
+        self.sources.push(src);
+        self
+    }
+
+    /// Sets the image shown before the video is played.
+    pub fn poster(mut self, poster: Location) -> Self {
+        self.poster = Some(poster);
+        self
+    }
+
+    /// Appends a text track.
+    pub fn track(mut self, track: Track) -> Self {
+        self.tracks.push(track);
+        self
+    }
+
+    /// Sets the width and height of the video in pixels.
+    pub fn size(mut self, width: u32, height: u32) -> Self {
+        self.width = Some(width);
+        self.height = Some(height);
+        self
+    }
+}
+
+impl Component for Video {
+    type Kind = BlockComponent;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        write!(fmt, "<video controls preload=\"metadata\" class=\"video\"")?;
+        if let Some(width) = self.width {
+            write!(fmt, " width=\"{}\"", width)?;
+        }
+        if let Some(height) = self.height {
+            write!(fmt, " height=\"{}\"", height)?;
+        }
+        if let Some(poster) = &self.poster {
+            write!(fmt, " poster=\"{}\"", ctx.renderer(poster))?;
+        }
+        write!(fmt, ">")?;
+
+        write_sources(fmt, ctx, MediaKind::Video, &self.sources)?;
+        for track in &self.tracks {
+            write!(fmt, "{}", ctx.renderer(track))?;
+        }
+
+        write_fallback(fmt, ctx, &self.sources)?;
+        write!(fmt, "</video>")
+    }
+}
+
+/// The kind of a text track.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TrackKind {
+    /// Translation of the dialogue.
+    Subtitles,
+    /// Transcription of the dialogue and sound effects.
+    Captions,
+    /// Description of the video contents.
+    Descriptions,
+    /// Titles of the chapters, for navigation.
+    Chapters,
+}
+
+impl TrackKind {
+    /// The value of the `kind` attribute.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TrackKind::Subtitles => "subtitles",
+            TrackKind::Captions => "captions",
+            TrackKind::Descriptions => "descriptions",
+            TrackKind::Chapters => "chapters",
+        }
+    }
+}
+
+/// A text track of a video, from a WebVTT (`.vtt`) file.
+#[derive(Debug, Clone)]
+pub struct Track {
+    /// Location of the WebVTT file.
+    pub src: Location,
+    /// Kind of the track.
+    pub kind: TrackKind,
+    /// Language of the track, such as "pt".
+    pub lang: Option<String>,
+    /// Title of the track shown to the user, such as "Portuguese".
+    pub label: String,
+    /// Whether the track is enabled by default.
+    pub default: bool,
+}
+
+impl Track {
+    /// Creates a track of subtitles in the given language.
+    pub fn subtitles<L, S>(src: Location, lang: L, label: S) -> Self
+    where
+        L: Into<String>,
+        S: Into<String>,
+    {
+        Self {
+            src,
+            kind: TrackKind::Subtitles,
+            lang: Some(lang.into()),
+            label: label.into(),
+            default: false,
+        }
+    }
+
+    /// Creates a track of captions in the given language.
+    pub fn captions<L, S>(src: Location, lang: L, label: S) -> Self
+    where
+        L: Into<String>,
+        S: Into<String>,
+    {
+        Self { kind: TrackKind::Captions, ..Self::subtitles(src, lang, label) }
+    }
+
+    /// Makes this track enabled by default.
+    pub fn default(mut self) -> Self {
+        self.default = true;
+        self
+    }
+}
+
+impl Component for Track {
+    type Kind = BlockComponent;
+
+    fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
+        write!(
+            fmt,
+            "<track kind=\"{}\" src=\"{}\" label=\"{}\"",
+            self.kind.as_str(),
+            ctx.renderer(&self.src),
+            ctx.renderer(&self.label),
+        )?;
+        if let Some(lang) = &self.lang {
+            write!(fmt, " srclang=\"{}\"", ctx.renderer(lang))?;
+        }
+        if self.default {
+            write!(fmt, " default")?;
+        }
+        write!(fmt, ">")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Track, Video};
+    use crate::{component::TestContext, location::Location};
+
+    #[test]
+    fn sources_and_tracks() {
+        let test = TestContext::new("langs/index.html");
+        let ctx = test.ctx();
+        let video = Video::new(Location::internal("video/speech.webm"))
+            .source(Location::internal("video/speech.mp4"))
+            .poster(Location::internal("img/speech.png"))
+            .track(
+                Track::subtitles(
+                    Location::internal("video/speech.pt.vtt"),
+                    "pt",
+                    "Português",
+                )
+                .default(),
+            )
+            .size(640, 360);
+        assert_eq!(
+            ctx.renderer(&video).to_string(),
+            "<video controls preload=\"metadata\" class=\"video\" \
+             width=\"640\" height=\"360\" \
+             poster=\"../img/speech.png\"><source \
+             src=\"../video/speech.webm\" type=\"video/webm\"><source \
+             src=\"../video/speech.mp4\" type=\"video/mp4\"><track \
+             kind=\"subtitles\" src=\"../video/speech.pt.vtt\" \
+             label=\"Português\" srclang=\"pt\" default><a \
+             href=\"../video/speech.webm\">../video/speech.webm</a></video>"
+        );
+    }
+}
@@ -0,0 +1,331 @@
+//! This module provides the glossary of the site: terms and abbreviations with
+//! their definitions, which pages can refer to and which can be linked
+//! automatically.
+
+use crate::{
+    component::Context,
+    location::{Id, InternalLoc, InternalPath},
+};
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, HashMap, HashSet},
+};
+
+/// A term or abbreviation of the glossary.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GlossaryEntry {
+    /// The definition of the term, or the expansion of the abbreviation.
+    pub definition: String,
+    /// Where the term is explained, such as a section about it.
+    pub location: Option<InternalLoc>,
+}
+
+impl GlossaryEntry {
+    /// Creates an entry with the given definition, not explained anywhere
+    /// else.
+    pub fn new<S>(definition: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self { definition: definition.into(), location: None }
+    }
+
+    /// Sets where the term is explained.
+    pub fn location(mut self, location: InternalLoc) -> Self {
+        self.location = Some(location);
+        self
+    }
+}
+
+/// The terms and abbreviations of the site.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Glossary {
+    /// The entries, indexed by term.
+    pub entries: BTreeMap<String, GlossaryEntry>,
+}
+
+impl Glossary {
+    /// Creates an empty glossary.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Finds the entry of a term.
+    pub fn get(&self, term: &str) -> Option<&GlossaryEntry> {
+        self.entries.get(term)
+    }
+
+    /// Inserts an entry under the given term, replacing any previous one.
+    pub fn insert<S>(&mut self, term: S, entry: GlossaryEntry)
+    where
+        S: Into<String>,
+    {
+        self.entries.insert(term.into(), entry);
+    }
+
+    /// The entries in alphabetical order, ignoring case.
+    pub fn sorted(&self) -> Vec<(&str, &GlossaryEntry)> {
+        let mut entries = self
+            .entries
+            .iter()
+            .map(|(term, entry)| (term.as_str(), entry))
+            .collect::<Vec<_>>();
+        entries.sort_by_cached_key(|&(term, _)| (term.to_lowercase(), term));
+        entries
+    }
+
+    /// The IDs of the terms in the glossary page, made from the terms
+    /// themselves. Terms giving the same ID are told apart by a numeric
+    /// suffix, in alphabetical order.
+    pub fn ids(&self) -> HashMap<&str, Id> {
+        let mut used = HashSet::new();
+        let mut ids = HashMap::new();
+        for (term, _) in self.sorted() {
+            ids.insert(term, Id::unique_slug(term, &mut used));
+        }
+        ids
+    }
+}
+
+/// Elements whose text is never linked to the glossary.
+const UNLINKED_ELEMENTS: &[&str] = &[
+    "a", "abbr", "button", "code", "h1", "h2", "h3", "h4", "h5", "h6", "math",
+    "pre", "rt", "script", "style", "svg", "title",
+];
+
+/// Links the first occurrence of each glossary term in the text of a rendered
+/// page. Terms are matched as whole words, case-sensitively, against the text
+/// with its character references such as `&amp;` decoded, outside of links,
+/// headings, code and math. Terms without a location of their own link to
+/// their entry in the glossary page, if any, and are otherwise left alone.
+pub(crate) fn link_terms(
+    html: &str,
+    ctx: Context,
+    glossary_page: Option<&InternalPath>,
+) -> String {
+    let glossary = &ctx.site().glossary;
+    let ids = glossary.ids();
+    let mut terms = glossary
+        .entries
+        .iter()
+        .filter(|(term, _)| !term.is_empty())
+        .filter_map(|(term, entry)| {
+            let href = match (&entry.location, glossary_page) {
+                (Some(location), _) => ctx.renderer(location).to_string(),
+                (None, Some(page)) => {
+                    let location = InternalLoc {
+                        path: page.clone(),
+                        id: ids.get(term.as_str()).cloned(),
+                    };
+                    ctx.renderer(location).to_string()
+                },
+                (None, None) => return None,
+            };
+            Some((
+                term.as_str(),
+                href,
+                ctx.renderer(&entry.definition).to_string(),
+            ))
+        })
+        .collect::<Vec<_>>();
+    // Longest terms first, so that "strong verb" wins over "verb".
+    terms.sort_by_key(|(term, _, _)| Reverse(term.len()));
+
+    let mut linked = HashSet::new();
+    let mut output = String::with_capacity(html.len());
+    let mut unlinked_depth = 0usize;
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        if rest.starts_with('<') {
+            let end = rest.find('>').map_or(rest.len(), |end| end + 1);
+            let tag = &rest[.. end];
+            let closing = tag.starts_with("</");
+            let name = tag
+                .trim_start_matches(['<', '/'])
+                .split(|ch: char| ch.is_whitespace() || ch == '>' || ch == '/')
+                .next()
+                .unwrap_or("")
+                .to_ascii_lowercase();
+            if UNLINKED_ELEMENTS.contains(&name.as_str()) {
+                if closing {
+                    unlinked_depth = unlinked_depth.saturating_sub(1);
+                } else if !tag.ends_with("/>") {
+                    unlinked_depth += 1;
+                }
+            }
+            output.push_str(tag);
+            rest = &rest[end ..];
+            continue;
+        }
+
+        let end = rest.find('<').unwrap_or(rest.len());
+        let text = &rest[.. end];
+        rest = &rest[end ..];
+        if unlinked_depth > 0 {
+            output.push_str(text);
+        } else {
+            link_text(text, &terms, &mut linked, &mut output);
+        }
+    }
+
+    output
+}
+
+/// Links the first occurrence of each term not linked yet in a piece of text
+/// without markup. Terms are given with their link and definition.
+fn link_text(
+    text: &str,
+    terms: &[(&str, String, String)],
+    linked: &mut HashSet<usize>,
+    output: &mut String,
+) {
+    let (decoded, offsets) = decode(text);
+    let mut done = 0;
+    let mut written = 0;
+    while let Some((start, index)) = terms
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| !linked.contains(index))
+        .filter_map(|(index, (term, _, _))| {
+            let start = done + find_word(&decoded[done ..], term)?;
+            Some((start, index))
+        })
+        .min()
+    {
+        let (term, href, definition) = &terms[index];
+        let end = start + term.len();
+        output.push_str(&text[written .. offsets[start]]);
+        output.push_str(&format!(
+            "<a href=\"{}\" title=\"{}\" class=\"glossary-link\">{}</a>",
+            href,
+            definition,
+            &text[offsets[start] .. offsets[end]]
+        ));
+        written = offsets[end];
+        done = end;
+        linked.insert(index);
+    }
+    output.push_str(&text[written ..]);
+}
+
+/// Decodes the character references of a text, such as `&amp;` or `&#39;`.
+/// References not known are decoded as U+FFFC, so that nothing matches
+/// inside them. Returns the decoded text and, for each of its bytes and its
+/// end, the matching offset in the original text.
+fn decode(text: &str) -> (String, Vec<usize>) {
+    let mut decoded = String::with_capacity(text.len());
+    let mut offsets = Vec::with_capacity(text.len() + 1);
+    let mut pos = 0;
+    while let Some(ch) = text[pos ..].chars().next() {
+        let (ch, len) = match ch {
+            '&' => {
+                let name_len = text[pos + 1 ..]
+                    .find(|ch: char| !ch.is_ascii_alphanumeric() && ch != '#')
+                    .unwrap_or(text.len() - pos - 1);
+                let end = pos + 1 + name_len;
+                if name_len > 0 && text[end ..].starts_with(';') {
+                    let name = &text[pos + 1 .. end];
+                    (
+                        decode_reference(name).unwrap_or('\u{fffc}'),
+                        end + 1 - pos,
+                    )
+                } else {
+                    ('&', 1)
+                }
+            },
+            ch => (ch, ch.len_utf8()),
+        };
+        for _ in 0 .. ch.len_utf8() {
+            offsets.push(pos);
+        }
+        decoded.push(ch);
+        pos += len;
+    }
+    offsets.push(text.len());
+    (decoded, offsets)
+}
+
+/// Decodes a character reference given without its `&` and `;`.
+fn decode_reference(name: &str) -> Option<char> {
+    match name {
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some('\u{a0}'),
+        _ => {
+            let number = name.strip_prefix('#')?;
+            let code = match number.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => number.parse().ok()?,
+            };
+            char::from_u32(code)
+        },
+    }
+}
+
+/// Finds a word in a text, not preceded or followed by alphanumeric
+/// characters.
+fn find_word(text: &str, word: &str) -> Option<usize> {
+    let mut offset = 0;
+    while let Some(start) = text[offset ..].find(word) {
+        let start = offset + start;
+        let end = start + word.len();
+        let before = text[.. start].chars().next_back();
+        let after = text[end ..].chars().next();
+        if !before.is_some_and(char::is_alphanumeric)
+            && !after.is_some_and(char::is_alphanumeric)
+        {
+            return Some(start);
+        }
+        offset = start + word.chars().next().map_or(1, char::len_utf8);
+    }
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::{link_terms, GlossaryEntry};
+    use crate::{
+        component::TestContext,
+        location::{InternalLoc, InternalPath},
+        site::Site,
+    };
+
+    #[test]
+    fn first_occurrence() {
+        let mut site = Site::default();
+        site.glossary.insert(
+            "ablaut",
+            GlossaryEntry::new("Vowel alternation")
+                .location(InternalLoc::parse("grammar#ablaut").unwrap()),
+        );
+        site.glossary.insert("NOM", GlossaryEntry::new("nominative"));
+        site.glossary.insert(
+            "O'odham",
+            GlossaryEntry::new("Uto-Aztecan language")
+                .location(InternalLoc::parse("langs/ood").unwrap()),
+        );
+        let glossary = InternalPath::parse("glossary.html").unwrap();
+        let test = TestContext::with_site(site, "langs/index.html");
+        let ctx = test.ctx();
+        let html = link_terms(
+            "<h2>ablaut</h2><p>NOMINAL &NOM; <math><mi>NOM</mi></math> \
+             ablaut, NOM and ablaut in Tohono O&#39;odham</p>",
+            ctx,
+            Some(&glossary),
+        );
+        assert_eq!(
+            html,
+            "<h2>ablaut</h2><p>NOMINAL &NOM; <math><mi>NOM</mi></math> <a \
+             href=\"../grammar#ablaut\" title=\"Vowel alternation\" \
+             class=\"glossary-link\">ablaut</a>, <a \
+             href=\"../glossary.html#nom\" title=\"nominative\" \
+             class=\"glossary-link\">NOM</a> and ablaut in Tohono <a \
+             href=\"../langs/ood\" title=\"Uto-Aztecan language\" \
+             class=\"glossary-link\">O&#39;odham</a></p>"
+        );
+    }
+}
@@ -0,0 +1,687 @@
+//! This module provides lexicons: dictionaries of a language, usually loaded
+//! from TOML files, from which a page per entry, alphabetical index pages and
+//! a reverse index are generated.
+
+use crate::{
+    component::{
+        lang::Lang,
+        list::{Definition, DefinitionList, OrderedList, UnorderedList},
+        text::{Bold, Italic, Link, Paragraph},
+        BlockComponent,
+        Component,
+        DynComponent,
+        InlineComponent,
+    },
+    location::{Fragment, Id, InternalLoc, InternalPath, Location},
+    page::{Page, Section},
+};
+use anyhow::{anyhow, Context as _};
+use serde::Deserialize;
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::Path,
+};
+
+/// A sense of a lexicon entry.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Sense {
+    /// Translations of the sense, used by the reverse index.
+    pub glosses: Vec<String>,
+    /// A longer explanation of the sense.
+    pub definition: Option<String>,
+}
+
+/// An entry of a lexicon.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LexiconEntry {
+    /// ID of the entry, naming its page.
+    pub id: Id,
+    /// The word being described.
+    pub headword: String,
+    /// The pronunciation of the headword, in IPA.
+    pub pronunciation: Option<String>,
+    /// The part of speech, such as "noun".
+    pub part_of_speech: Option<String>,
+    /// The senses of the word, in order.
+    pub senses: Vec<Sense>,
+    /// Where the word comes from.
+    pub etymology: Option<String>,
+    /// IDs of related entries of the same lexicon.
+    pub see_also: Vec<Id>,
+}
+
+impl LexiconEntry {
+    /// Creates an entry without pronunciation, senses or etymology.
+    pub fn new<S>(id: Id, headword: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            id,
+            headword: headword.into(),
+            pronunciation: None,
+            part_of_speech: None,
+            senses: Vec::new(),
+            etymology: None,
+            see_also: Vec::new(),
+        }
+    }
+}
+
+/// The alphabetical order of a lexicon. Letters may have more than one
+/// character, such as "ch", and are compared ignoring case. Characters which
+/// are not letters of the alphabet are sorted after them, by code point.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Collation {
+    /// The letters of the alphabet, in order, in lowercase.
+    pub letters: Vec<String>,
+}
+
+impl Collation {
+    /// Creates the collation of the given alphabet.
+    pub fn new<I, S>(letters: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let letters = letters
+            .into_iter()
+            .map(|letter| letter.into().to_lowercase())
+            .collect();
+        Self { letters }
+    }
+
+    /// Splits the first letter of the given lowercase word, returning its
+    /// sort key and the rest of the word.
+    fn next_letter<'word>(
+        &self,
+        word: &'word str,
+    ) -> Option<(u32, &'word str)> {
+        let letter = self
+            .letters
+            .iter()
+            .enumerate()
+            .filter(|(_, letter)| {
+                !letter.is_empty() && word.starts_with(letter.as_str())
+            })
+            .max_by_key(|(_, letter)| letter.len());
+        match letter {
+            Some((index, letter)) => {
+                Some((index as u32, &word[letter.len() ..]))
+            },
+            None => {
+                let ch = word.chars().next()?;
+                Some((
+                    self.letters.len() as u32 + ch as u32,
+                    &word[ch.len_utf8() ..],
+                ))
+            },
+        }
+    }
+
+    /// The sort key of a word.
+    pub fn key(&self, word: &str) -> Vec<u32> {
+        let word = word.to_lowercase();
+        let mut key = Vec::new();
+        let mut rest = word.as_str();
+        while let Some((letter, after)) = self.next_letter(rest) {
+            key.push(letter);
+            rest = after;
+        }
+        key
+    }
+
+    /// The first letter of a word, capitalized, under which it is indexed.
+    pub fn initial(&self, word: &str) -> String {
+        let word = word.to_lowercase();
+        let initial = match self.next_letter(&word) {
+            Some((_, rest)) => &word[.. word.len() - rest.len()],
+            None => "",
+        };
+        let mut chars = initial.chars();
+        chars
+            .next()
+            .map(|first| first.to_uppercase().chain(chars).collect())
+            .unwrap_or_default()
+    }
+}
+
+/// A dictionary of a language, whose pages are generated under a prefix.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lexicon {
+    /// The title of the index page.
+    pub title: String,
+    /// The directory of the generated pages.
+    pub prefix: InternalPath,
+    /// The language tag of the headwords.
+    pub lang: Option<String>,
+    /// The alphabetical order of the headwords.
+    pub collation: Collation,
+    /// The entries, in any order.
+    pub entries: Vec<LexiconEntry>,
+}
+
+/// A lexicon file, as written. Internal use.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawLexicon {
+    #[serde(default)]
+    entries: Vec<RawEntry>,
+}
+
+/// A lexicon entry, as written. Internal use.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+struct RawEntry {
+    id: Option<String>,
+    headword: String,
+    pronunciation: Option<String>,
+    part_of_speech: Option<String>,
+    #[serde(default)]
+    senses: Vec<RawSense>,
+    etymology: Option<String>,
+    #[serde(default)]
+    see_also: Vec<String>,
+}
+
+/// A sense, as written. Internal use.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawSense {
+    #[serde(default)]
+    glosses: Vec<String>,
+    definition: Option<String>,
+}
+
+impl Lexicon {
+    /// Creates an empty lexicon generated under the given prefix.
+    pub fn new<S>(title: S, prefix: InternalPath) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            title: title.into(),
+            prefix,
+            lang: None,
+            collation: Collation::default(),
+            entries: Vec::new(),
+        }
+    }
+
+    /// Sets the language tag of the headwords.
+    pub fn lang<S>(mut self, lang: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.lang = Some(lang.into());
+        self
+    }
+
+    /// Sets the alphabetical order of the headwords.
+    pub fn collation(mut self, collation: Collation) -> Self {
+        self.collation = collation;
+        self
+    }
+
+    /// Adds an entry.
+    pub fn insert(&mut self, entry: LexiconEntry) {
+        self.entries.push(entry);
+    }
+
+    /// Loads every entry of a lexicon TOML file into this lexicon.
+    pub fn load_toml<P>(&mut self, path: P) -> anyhow::Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Reading lexicon {}", path.display()))?;
+        self.parse_toml(&contents)
+            .with_context(|| format!("Parsing lexicon {}", path.display()))
+    }
+
+    /// Parses lexicon entries from TOML source, such as:
+    ///
+    /// ```toml
+    /// [[entries]]
+    /// headword = "lupus"
+    /// pronunciation = "ˈlu.pus"
+    /// part-of-speech = "noun"
+    /// etymology = "From Proto-Italic *lukʷos."
+    /// see-also = ["lupa"]
+    ///
+    /// [[entries.senses]]
+    /// glosses = ["wolf"]
+    /// ```
+    ///
+    /// The ID of an entry defaults to a slug of its headword (see
+    /// [`Id::slug`]), with a numeric suffix for homographs, such as `lupus-2`.
+    pub fn parse_toml(&mut self, source: &str) -> anyhow::Result<()> {
+        let raw = toml::from_str::<RawLexicon>(source)?;
+        let mut used = self
+            .entries
+            .iter()
+            .map(|entry| entry.id.clone())
+            .collect::<HashSet<_>>();
+        for entry in &raw.entries {
+            if let Some(id) = &entry.id {
+                let id = Id::new(id.as_str())
+                    .with_context(|| format!("Entry {:?}", entry.headword))?;
+                used.insert(id);
+            }
+        }
+        for entry in raw.entries {
+            let id = match &entry.id {
+                Some(id) => Id::new(id.as_str())?,
+                None => Id::unique_slug(&entry.headword, &mut used),
+            };
+            let see_also = entry
+                .see_also
+                .iter()
+                .map(|id| Id::new(id.as_str()))
+                .collect::<anyhow::Result<_>>()
+                .with_context(|| format!("Entry {:?}", entry.headword))?;
+            self.insert(LexiconEntry {
+                id,
+                headword: entry.headword,
+                pronunciation: entry.pronunciation,
+                part_of_speech: entry.part_of_speech,
+                senses: entry
+                    .senses
+                    .into_iter()
+                    .map(|sense| Sense {
+                        glosses: sense.glosses,
+                        definition: sense.definition,
+                    })
+                    .collect(),
+                etymology: entry.etymology,
+                see_also,
+            });
+        }
+        Ok(())
+    }
+
+    /// The entries in alphabetical order.
+    pub fn sorted(&self) -> Vec<&LexiconEntry> {
+        let mut entries = self.entries.iter().collect::<Vec<_>>();
+        entries.sort_by_cached_key(|entry| {
+            (self.collation.key(&entry.headword), entry.id.clone())
+        });
+        entries
+    }
+
+    /// Path to the page of an entry.
+    pub fn entry_path(&self, id: &Id) -> anyhow::Result<InternalPath> {
+        self.page_path(format!("{}.html", id))
+    }
+
+    /// Path to a page under the prefix. Internal use.
+    fn page_path(&self, name: String) -> anyhow::Result<InternalPath> {
+        Ok(self.prefix.clone().append(Fragment::new(name)?))
+    }
+
+    /// Generates the pages of the lexicon: one page per entry, the index at
+    /// `index.html` linking to a page per initial letter, named after it like
+    /// `letter-ch.html`, and the reverse index at `reverse.html`. Fails if two
+    /// pages get the same path.
+    pub fn pages(&self) -> anyhow::Result<Vec<(InternalPath, Page)>> {
+        let by_id = self
+            .entries
+            .iter()
+            .map(|entry| (&entry.id, entry))
+            .collect::<HashMap<_, _>>();
+        if by_id.len() != self.entries.len() {
+            Err(anyhow!("Duplicated entry IDs in lexicon {}", self.title))?;
+        }
+
+        let mut pages = Vec::new();
+        for entry in &self.entries {
+            let page = self.entry_page(entry, &by_id).with_context(|| {
+                format!("Entry {} of lexicon {}", entry.id, self.title)
+            })?;
+            pages.push((self.entry_path(&entry.id)?, page));
+        }
+
+        let mut letters = Vec::<(String, Vec<&LexiconEntry>)>::new();
+        for entry in self.sorted() {
+            let initial = self.collation.initial(&entry.headword);
+            match letters.last_mut() {
+                Some((letter, entries)) if *letter == initial => {
+                    entries.push(entry)
+                },
+                _ => letters.push((initial, vec![entry])),
+            }
+        }
+
+        let mut letter_links = Vec::new();
+        let mut letter_ids = HashSet::new();
+        for (letter, entries) in &letters {
+            let id = Id::unique_slug(letter, &mut letter_ids);
+            let path = self.page_path(format!("letter-{}.html", id))?;
+            letter_links.push(self.link(letter.clone(), path.clone()));
+            let items = entries
+                .iter()
+                .map(|entry| self.summary(entry))
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            pages.push((
+                path,
+                Page {
+                    title: format!("{}: {}", self.title, letter),
+                    body: UnorderedList(items).to_dyn(),
+                    sections: Vec::new(),
+                    lang: None,
+                },
+            ));
+        }
+
+        let reverse_path = self.page_path(String::from("reverse.html"))?;
+        let index = vec![
+            Paragraph(join(letter_links, " · ")).to_dyn(),
+            Paragraph(
+                self.link(String::from("Reverse index"), reverse_path.clone()),
+            )
+            .to_dyn(),
+        ];
+        pages.push((
+            self.page_path(String::from("index.html"))?,
+            Page {
+                title: self.title.clone(),
+                body: index.to_dyn(),
+                sections: Vec::new(),
+                lang: None,
+            },
+        ));
+
+        pages.push((
+            reverse_path,
+            Page {
+                title: format!("{}: reverse index", self.title),
+                body: self.reverse_index()?.to_dyn(),
+                sections: Vec::new(),
+                lang: None,
+            },
+        ));
+
+        let mut paths = HashSet::new();
+        for (path, _) in &pages {
+            if !paths.insert(path) {
+                Err(anyhow!(
+                    "Page {} of lexicon {} is generated twice",
+                    path,
+                    self.title
+                ))?;
+            }
+        }
+
+        Ok(pages)
+    }
+
+    /// The page of an entry. Internal use.
+    fn entry_page(
+        &self,
+        entry: &LexiconEntry,
+        by_id: &HashMap<&Id, &LexiconEntry>,
+    ) -> anyhow::Result<Page> {
+        let mut head = vec![Bold(self.headword(entry)).to_dyn()];
+        if let Some(pronunciation) = &entry.pronunciation {
+            head.push(format!(" /{}/", pronunciation).to_dyn());
+        }
+        if let Some(part_of_speech) = &entry.part_of_speech {
+            head.push(String::from(" ").to_dyn());
+            head.push(Italic(part_of_speech.clone()).to_dyn());
+        }
+
+        let senses = entry
+            .senses
+            .iter()
+            .map(|sense| {
+                let mut item = vec![Bold(sense.glosses.join(", ")).to_dyn()];
+                if let Some(definition) = &sense.definition {
+                    let separator =
+                        if sense.glosses.is_empty() { "" } else { ": " };
+                    item.push(format!("{}{}", separator, definition).to_dyn());
+                }
+                item
+            })
+            .collect::<Vec<_>>();
+        let body: Vec<DynComponent<BlockComponent>> =
+            vec![Paragraph(head).to_dyn(), OrderedList(senses).to_dyn()];
+
+        let mut sections = Vec::new();
+        if let Some(etymology) = &entry.etymology {
+            sections.push(Section {
+                title: String::from("Etymology").to_dyn(),
+                body: Paragraph(etymology.clone()).to_dyn(),
+                id: Id::new("etymology")?,
+                children: Vec::new(),
+            });
+        }
+        if !entry.see_also.is_empty() {
+            let links = entry
+                .see_also
+                .iter()
+                .map(|id| {
+                    let other = by_id.get(id).ok_or_else(|| {
+                        anyhow!("Unknown cross-referenced entry {}", id)
+                    })?;
+                    Ok(self.link(other.headword.clone(), self.entry_path(id)?))
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            sections.push(Section {
+                title: String::from("See also").to_dyn(),
+                body: UnorderedList(links).to_dyn(),
+                id: Id::new("see-also")?,
+                children: Vec::new(),
+            });
+        }
+
+        Ok(Page {
+            title: entry.headword.clone(),
+            body: body.to_dyn(),
+            sections,
+            lang: None,
+        })
+    }
+
+    /// A link to an entry followed by its glosses, as listed in the index.
+    /// Internal use.
+    fn summary(
+        &self,
+        entry: &LexiconEntry,
+    ) -> anyhow::Result<DynComponent<InlineComponent>> {
+        let glosses = entry
+            .senses
+            .iter()
+            .flat_map(|sense| &sense.glosses)
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        let mut summary = vec![self.link_entry(entry)?];
+        if let Some(part_of_speech) = &entry.part_of_speech {
+            summary.push(String::from(" ").to_dyn());
+            summary.push(Italic(part_of_speech.clone()).to_dyn());
+        }
+        if !glosses.is_empty() {
+            summary.push(format!(" — {}", glosses.join(", ")).to_dyn());
+        }
+        Ok(summary.to_dyn())
+    }
+
+    /// The reverse index: every gloss in alphabetical order, with links to
+    /// the entries it translates. Internal use.
+    fn reverse_index(
+        &self,
+    ) -> anyhow::Result<DefinitionList<String, DynComponent<InlineComponent>>>
+    {
+        let mut glosses = Vec::new();
+        for entry in self.sorted() {
+            for sense in &entry.senses {
+                for gloss in &sense.glosses {
+                    glosses.push((gloss.to_lowercase(), gloss, entry));
+                }
+            }
+        }
+        glosses.sort_by(|(left, ..), (right, ..)| left.cmp(right));
+        glosses.dedup_by(|(_, left, left_entry), (_, right, right_entry)| {
+            left == right && left_entry.id == right_entry.id
+        });
+
+        let mut list = Vec::<Definition<String, _>>::new();
+        for (_, gloss, entry) in glosses {
+            let link = self.link_entry(entry)?;
+            match list.last_mut() {
+                Some(definition) if definition.term == *gloss => {
+                    definition.definitions.push(link)
+                },
+                _ => list.push(Definition {
+                    term: gloss.clone(),
+                    definitions: vec![link],
+                }),
+            }
+        }
+        Ok(DefinitionList(list))
+    }
+
+    /// A link to the page of an entry, showing its headword. Internal use.
+    fn link_entry(
+        &self,
+        entry: &LexiconEntry,
+    ) -> anyhow::Result<DynComponent<InlineComponent>> {
+        Ok(Link {
+            text: self.headword(entry),
+            location: Location::Internal(InternalLoc::from(
+                self.entry_path(&entry.id)?,
+            )),
+        }
+        .to_dyn())
+    }
+
+    /// A link to a page of the lexicon. Internal use.
+    fn link(
+        &self,
+        text: String,
+        path: InternalPath,
+    ) -> DynComponent<InlineComponent> {
+        Link { text, location: Location::Internal(InternalLoc::from(path)) }
+            .to_dyn()
+    }
+
+    /// The headword of an entry, tagged with the language of the lexicon.
+    /// Internal use.
+    fn headword(&self, entry: &LexiconEntry) -> DynComponent<InlineComponent> {
+        match &self.lang {
+            Some(lang) => {
+                Lang::new(lang.clone(), entry.headword.clone()).to_dyn()
+            },
+            None => entry.headword.clone().to_dyn(),
+        }
+    }
+}
+
+/// Joins inline components with a separator. Internal use.
+fn join(
+    parts: Vec<DynComponent<InlineComponent>>,
+    separator: &'static str,
+) -> Vec<DynComponent<InlineComponent>> {
+    let mut joined = Vec::with_capacity(parts.len() * 2);
+    for (i, part) in parts.into_iter().enumerate() {
+        if i > 0 {
+            joined.push(separator.to_dyn());
+        }
+        joined.push(part);
+    }
+    joined
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Collation, Lexicon};
+    use crate::{location::InternalPath, page::RenderPage, site::Site};
+
+    #[test]
+    fn pages_and_indices() {
+        let mut lexicon = Lexicon::new(
+            "Divine lexicon",
+            InternalPath::parse("langs/div/lexicon").unwrap(),
+        )
+        .lang("art-x-div")
+        .collation(Collation::new(["a", "c", "ch", "d"]));
+        lexicon
+            .parse_toml(concat!(
+                "[[entries]]\n",
+                "headword = \"da\"\n",
+                "[[entries.senses]]\n",
+                "glosses = [\"give\"]\n",
+                "[[entries]]\n",
+                "headword = \"cha\"\n",
+                "see-also = [\"ca\"]\n",
+                "[[entries.senses]]\n",
+                "glosses = [\"tea\", \"give\"]\n",
+                "[[entries]]\n",
+                "headword = \"ca\"\n",
+            ))
+            .unwrap();
+        let sorted = lexicon
+            .sorted()
+            .iter()
+            .map(|entry| entry.headword.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(sorted, ["ca", "cha", "da"]);
+        assert_eq!(lexicon.collation.initial("cha"), "Ch");
+
+        let pages = lexicon.pages().unwrap();
+        let paths =
+            pages.iter().map(|(path, _)| path.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            paths,
+            [
+                "langs/div/lexicon/da.html",
+                "langs/div/lexicon/cha.html",
+                "langs/div/lexicon/ca.html",
+                "langs/div/lexicon/letter-c.html",
+                "langs/div/lexicon/letter-ch.html",
+                "langs/div/lexicon/letter-d.html",
+                "langs/div/lexicon/index.html",
+                "langs/div/lexicon/reverse.html",
+            ]
+        );
+
+        let site = Site::default();
+        let (location, page) = &pages[7];
+        let mut html = Vec::new();
+        RenderPage { page, location, site: &site }.write_to(&mut html).unwrap();
+        let html = String::from_utf8(html).unwrap();
+        assert!(html.contains(
+            "<dt>give</dt><dd><a href=\"../../../langs/div/lexicon/cha.html\" \
+             class=\"link\"><span lang=\"art-x-div\" \
+             class=\"lang\">cha</span></a></dd><dd><a \
+             href=\"../../../langs/div/lexicon/da.html\" class=\"link\"><span \
+             lang=\"art-x-div\" class=\"lang\">da</span></a></dd>"
+        ));
+        assert!(lexicon
+            .parse_toml("[[entries]]\nid = \"bad word\"\nheadword = \"bad\"\n")
+            .is_err());
+
+        lexicon
+            .parse_toml(concat!(
+                "[[entries]]\n",
+                "headword = \"Dà\"\n",
+                "[[entries]]\n",
+                "headword = \"жа ба\"\n",
+                "[[entries]]\n",
+                "id = \"letter-d\"\n",
+                "headword = \"dad\"\n",
+            ))
+            .unwrap();
+        let ids = lexicon
+            .entries
+            .iter()
+            .map(|entry| entry.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ids,
+            ["da", "cha", "ca", "da-2", "u436-u430-u431-u430", "letter-d"]
+        );
+        assert!(lexicon.pages().is_err());
+    }
+}
@@ -7,3 +7,8 @@ pub mod component;
 pub mod page;
 pub mod site;
 pub mod fmt;
+pub mod bib;
+pub mod asset;
+pub mod template;
+pub mod glossary;
+pub mod lexicon;
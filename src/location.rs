@@ -2,8 +2,14 @@
 
 use crate::component::{Component, Context, InlineComponent};
 use percent_encoding::{percent_encode, CONTROLS};
-use std::{fmt, path::PathBuf, str};
+use std::{
+    collections::HashSet,
+    fmt::{self, Write as _},
+    path::PathBuf,
+    str,
+};
 use thiserror::Error;
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
 use url::Url;
 
 /// A location of a page, either internal or external.
@@ -50,6 +56,17 @@ impl Location {
             InternalLoc::parse(contents).expect("bad internal location"),
         )
     }
+
+    /// The extension of the file this location points to, without the dot,
+    /// such as "mp4". `None` if the last piece of the path has no extension.
+    pub fn extension(&self) -> Option<&str> {
+        let name = match self {
+            Location::Url(url) => url.path().rsplit('/').next()?,
+            Location::Internal(int) => int.path.fragments.last()?.as_str(),
+        };
+        let dot = name.rfind('.')?;
+        Some(&name[dot + 1 ..]).filter(|ext| !ext.is_empty())
+    }
 }
 
 impl Component for Location {
@@ -131,6 +148,19 @@ impl InternalPath {
             self == other
         }
     }
+
+    /// Makes an URL to this path relative to the given page location, not
+    /// escaped for HTML. Empty if both point to the same page.
+    pub fn relative_url(&self, from: &InternalPath) -> String {
+        let mut url = String::new();
+        if !self.eq_index(from) {
+            for _ in 0 .. from.dir_depth() {
+                url.push_str("../");
+            }
+            url.extend(percent_encode(self.to_string().as_bytes(), CONTROLS));
+        }
+        url
+    }
 }
 
 impl Default for InternalPath {
@@ -160,15 +190,7 @@ impl Component for InternalPath {
     type Kind = InlineComponent;
 
     fn to_html(&self, fmt: &mut fmt::Formatter, ctx: Context) -> fmt::Result {
-        if !self.eq_index(ctx.location()) {
-            for _ in 0 .. ctx.location().dir_depth() {
-                fmt.write_str("../")?;
-            }
-            let encoded = percent_encode(self.to_string().as_bytes(), CONTROLS)
-                .collect::<String>();
-            write!(fmt, "{}", ctx.renderer(&encoded))?;
-        }
-        Ok(())
+        write!(fmt, "{}", ctx.renderer(self.relative_url(ctx.location())))
     }
 }
 
@@ -212,6 +234,17 @@ impl InternalLoc {
             },
         })
     }
+
+    /// Makes an URL to this location relative to the given page location, not
+    /// escaped for HTML.
+    pub fn relative_url(&self, from: &InternalPath) -> String {
+        let mut url = self.path.relative_url(from);
+        if let Some(id) = &self.id {
+            url.push('#');
+            url.push_str(id.as_str());
+        }
+        url
+    }
 }
 
 impl fmt::Display for InternalLoc {
@@ -270,6 +303,48 @@ impl Id {
         Ok(Self { contents: contents.into() })
     }
 
+    /// Creates an ID out of any text, such as a headword. Diacritics are
+    /// removed, letters are lowercased, other Latin letters and digits are
+    /// kept, non-Latin letters are written as their code point, like `u436`,
+    /// and everything else becomes '-'. IDs not starting with a letter are
+    /// prefixed by `x-`.
+    pub fn slug(text: &str) -> Self {
+        let mut slug = String::with_capacity(text.len());
+        for ch in text.nfd().filter(|&ch| !is_combining_mark(ch)) {
+            if ch.is_ascii_alphanumeric() {
+                slug.push(ch.to_ascii_lowercase());
+            } else if ch.is_alphanumeric() {
+                let lower = ch.to_lowercase().next().unwrap_or(ch);
+                if !slug.is_empty() && !slug.ends_with('-') {
+                    slug.push('-');
+                }
+                write!(slug, "u{:x}-", u32::from(lower)).unwrap();
+            } else if !slug.is_empty() && !slug.ends_with('-') {
+                slug.push('-');
+            }
+        }
+        if !slug.starts_with(|ch: char| ch.is_ascii_alphabetic()) {
+            slug.insert_str(0, "x-");
+        }
+        Self::new(slug.trim_end_matches('-').to_owned())
+            .expect("slugs are valid IDs")
+    }
+
+    /// Creates an ID out of any text, like [`Id::slug`], different from every
+    /// ID already used. Clashing slugs are given a numeric suffix, starting
+    /// at `-2`. The ID is recorded as used.
+    pub fn unique_slug(text: &str, used: &mut HashSet<Id>) -> Self {
+        let slug = Self::slug(text);
+        let mut id = slug.clone();
+        let mut suffix = 1;
+        while !used.insert(id.clone()) {
+            suffix += 1;
+            id = Self::new(format!("{}-{}", slug, suffix))
+                .expect("suffixed slugs are valid IDs");
+        }
+        id
+    }
+
     /// The string contents of this ID.
     pub fn as_str(&self) -> &str {
         &self.contents
@@ -337,7 +412,20 @@ impl fmt::Display for Fragment {
 
 #[cfg(test)]
 mod test {
-    use super::InternalPath;
+    use super::{Id, InternalPath};
+    use std::collections::HashSet;
+
+    #[test]
+    fn slugs() {
+        assert_eq!(Id::slug("Strong verb").as_str(), "strong-verb");
+        assert_eq!(Id::slug("ça-ç'à").as_str(), "ca-c-a");
+        assert_eq!(Id::slug("ж1").as_str(), "u436-1");
+        assert_eq!(Id::slug("1st").as_str(), "x-1st");
+        assert_eq!(Id::slug("").as_str(), "x");
+        let mut used = HashSet::new();
+        assert_eq!(Id::unique_slug("Ablaut", &mut used).as_str(), "ablaut");
+        assert_eq!(Id::unique_slug("ablaut", &mut used).as_str(), "ablaut-2");
+    }
 
     #[test]
     fn eq_index() {
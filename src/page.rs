@@ -2,11 +2,25 @@
 //! inner components.
 
 use crate::{
-    component::{Context, DynComponent, InlineComponent},
-    location::{Id, InternalLoc, InternalPath},
-    site::Site,
+    asset::Assets,
+    component::{
+        cite::References,
+        note::{Note, Notes},
+        Component,
+        Context,
+        DynComponent,
+        InlineComponent,
+    },
+    location::{Fragment, Id, InternalLoc, InternalPath},
+    site::{Node, Site},
+};
+use anyhow::{anyhow, Context as _};
+use std::{
+    cell::{Cell, RefCell},
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fmt::{self, Write as _},
+    io,
 };
-use std::fmt;
 
 /// A section of the page, on either top-level, nested in one level, nested in
 /// two leves, etc.
@@ -22,6 +36,13 @@ pub struct Section {
     pub children: Vec<Section>,
 }
 
+impl Section {
+    /// Tests if this section or any of its children has the given ID.
+    fn has_id(&self, id: &Id) -> bool {
+        self.id == *id || self.children.iter().any(|child| child.has_id(id))
+    }
+}
+
 /// Internal (private) section renderer.
 #[derive(Debug, Clone, Copy)]
 struct RenderSection<'section, 'loc, 'site> {
@@ -83,6 +104,9 @@ pub struct Page {
     pub body: DynComponent,
     /// Child sections of the page.
     pub sections: Vec<Section>,
+    /// The language of the page, such as `pt`, set on its `<html>` element
+    /// instead of the language of the site.
+    pub lang: Option<String>,
 }
 
 impl AsRef<Page> for Page {
@@ -108,20 +132,52 @@ pub struct RenderPage<'page, 'loc, 'site> {
     pub site: &'site Site,
 }
 
-impl<'page, 'loc, 'site> fmt::Display for RenderPage<'page, 'loc, 'site> {
-    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
-        let ctx = Context::new(self.location, self.site);
+impl<'page, 'loc, 'site> RenderPage<'page, 'loc, 'site> {
+    /// Renders the page with the given rendering state. Internal use.
+    fn render(
+        &self,
+        fmt: &mut fmt::Formatter,
+        state: &PageState,
+    ) -> fmt::Result {
+        let ctx = Context::new(self.location, self.site, state);
         write!(
             fmt,
-            "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><meta \
+            "<!DOCTYPE html><html{lang}><head><meta charset=\"utf-8\"><meta \
              name=\"viewport\" content=\"width=device-width, \
              initial-scale=1.0\"><link rel=\"stylesheet\" type=\"text/css\" \
-             href=\"{css}\"><title>{title}</title><body><div \
-             id=\"page-top\"><div id=\"banner\"><a href=\"{home}\">The World \
-             From The Stars</a></div><h1>{title}</h1><div \
-             id=\"body-wrapper\">{body}",
+             href=\"{css}\"><title>{title}</title>",
+            lang = match self.page.lang.as_ref().or(self.site.lang.as_ref()) {
+                Some(lang) => format!(" lang=\"{}\"", ctx.renderer(lang)),
+                None => String::new(),
+            },
             css = ctx.renderer(InternalPath::parse("css/main.css").unwrap()),
             title = ctx.renderer(&self.page.title),
+        )?;
+        for style in state.styles() {
+            write!(fmt, "<style>{}</style>", style)?;
+        }
+        for (family, path) in state.fonts() {
+            write!(
+                fmt,
+                "<style>@font-face {{ font-family: \"{}\"; src: url(\"{}\"); \
+                 font-display: swap; }}</style>",
+                family,
+                ctx.renderer(path)
+            )?;
+        }
+        for path in state.scripts().keys() {
+            write!(
+                fmt,
+                "<script src=\"{}\" defer></script>",
+                ctx.renderer(InternalPath::parse(path).unwrap())
+            )?;
+        }
+        write!(
+            fmt,
+            "<body><div id=\"page-top\"><div id=\"banner\"><a \
+             href=\"{home}\">The World From The \
+             Stars</a></div><h1>{title}</h1><div id=\"body-wrapper\">{body}",
+            title = ctx.renderer(&self.page.title),
             home = ctx.renderer(InternalPath::parse("").unwrap()),
             body = ctx.renderer(&self.page.body),
         )?;
@@ -130,9 +186,446 @@ impl<'page, 'loc, 'site> fmt::Display for RenderPage<'page, 'loc, 'site> {
             write!(fmt, "{}", RenderSection { level: 1, ctx, section })?;
         }
 
+        if state.has_notes() {
+            self.render_generated(fmt, ctx, "Notes", Notes.to_dyn(), "notes")?;
+        }
+
+        if state.has_citations() {
+            self.render_generated(
+                fmt,
+                ctx,
+                "References",
+                References.to_dyn(),
+                "references",
+            )?;
+        }
+
         write!(fmt, "</div></div></body></html>")?;
         Ok(())
     }
+
+    /// Renders a section generated after every other section, such as the
+    /// notes. Its ID must not be used by a section of the page.
+    fn render_generated(
+        &self,
+        fmt: &mut fmt::Formatter,
+        ctx: Context,
+        title: &str,
+        body: DynComponent,
+        id: &str,
+    ) -> fmt::Result {
+        let id = Id::new(id).unwrap();
+        if self.page.sections.iter().any(|section| section.has_id(&id)) {
+            Err(ctx.report(anyhow!(
+                "Section ID {} is reserved for the generated {} section",
+                id,
+                title
+            )))?;
+        }
+        let section = Section {
+            title: String::from(title).to_dyn(),
+            body,
+            id,
+            children: Vec::new(),
+        };
+        write!(fmt, "{}", RenderSection { level: 1, ctx, section: &section })
+    }
+
+    /// Renders the page into the given output. Unlike the [`fmt::Display`]
+    /// implementation, errors found by components while rendering, such as
+    /// broken references, are returned.
+    pub fn write_to<W>(&self, output: W) -> anyhow::Result<()>
+    where
+        W: io::Write,
+    {
+        self.write_state(output, None)?;
+        Ok(())
+    }
+
+    /// Renders the page into the given output, like [`RenderPage::write_to`],
+    /// checking the files referenced by the page against the given assets and
+    /// describing them, such as the dimensions of images.
+    pub fn write_with_assets<W>(
+        &self,
+        output: W,
+        assets: &Assets,
+    ) -> anyhow::Result<()>
+    where
+        W: io::Write,
+    {
+        self.write_state(output, Some(assets))?;
+        Ok(())
+    }
+
+    /// Renders the page into the given output, returning the state collected
+    /// while rendering. Internal use.
+    pub(crate) fn write_state<W>(
+        &self,
+        mut output: W,
+        assets: Option<&Assets>,
+    ) -> anyhow::Result<PageState>
+    where
+        W: io::Write,
+    {
+        let (html, state) = self.render_html(assets)?;
+        output.write_all(html.as_bytes())?;
+        output.flush()?;
+        Ok(state)
+    }
+
+    /// Renders the page into a string, with the state collected while
+    /// rendering. Internal use.
+    fn render_html(
+        &self,
+        assets: Option<&Assets>,
+    ) -> anyhow::Result<(String, PageState)> {
+        let state = PageState::with_assets(assets.cloned());
+        let mut html = String::new();
+        let result =
+            write!(html, "{}", RenderPasses { page: self, state: &state });
+        if let Some(error) = state.take_error() {
+            Err(error)
+                .with_context(|| format!("Rendering page {}", self.location))?;
+        }
+        result.map_err(|_| anyhow!("Rendering page {}", self.location))?;
+        Ok((html, state))
+    }
+}
+
+/// Displays the rendered page, or the text of the error found while rendering
+/// it. Use [`RenderPage::write_to`] in order to handle errors.
+impl<'page, 'loc, 'site> fmt::Display for RenderPage<'page, 'loc, 'site> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self.render_html(None) {
+            Ok((html, _)) => fmt.write_str(&html),
+            Err(error) => write!(fmt, "{:#}", error),
+        }
+    }
+}
+
+/// Every rendering pass over a page: the first one only collects state such as
+/// numbered IDs, so that they can be referred to before they appear. Internal
+/// use.
+#[derive(Debug, Clone, Copy)]
+struct RenderPasses<'render, 'page, 'loc, 'site> {
+    page: &'render RenderPage<'page, 'loc, 'site>,
+    state: &'render PageState,
+}
+
+impl<'render, 'page, 'loc, 'site> fmt::Display
+    for RenderPasses<'render, 'page, 'loc, 'site>
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(Sink, "{}", RenderPass { page: self.page, state: self.state })?;
+        self.state.start_final_pass();
+        self.page.render(fmt, self.state)
+    }
+}
+
+/// A single rendering pass over a page. Internal use.
+#[derive(Debug, Clone, Copy)]
+struct RenderPass<'render, 'page, 'loc, 'site> {
+    page: &'render RenderPage<'page, 'loc, 'site>,
+    state: &'render PageState,
+}
+
+impl<'render, 'page, 'loc, 'site> fmt::Display
+    for RenderPass<'render, 'page, 'loc, 'site>
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        self.page.render(fmt, self.state)
+    }
+}
+
+/// Output that discards everything written to it. Internal use.
+#[derive(Debug, Clone, Copy)]
+struct Sink;
+
+impl fmt::Write for Sink {
+    fn write_str(&mut self, _string: &str) -> fmt::Result {
+        Ok(())
+    }
+}
+
+/// State shared by the components of a page during its rendering, such as
+/// counters. Internal use.
+#[derive(Debug, Default)]
+pub(crate) struct PageState {
+    /// Whether this is the final pass, the one actually written.
+    final_pass: Cell<bool>,
+    /// Current value of each counter.
+    counters: RefCell<HashMap<&'static str, u32>>,
+    /// Numbers given to IDs.
+    numbers: RefCell<HashMap<Id, u32>>,
+    /// Numbers given to IDs in other pages, collected on demand.
+    foreign_numbers: RefCell<HashMap<InternalPath, HashMap<Id, u32>>>,
+    /// Footnotes, in the order they were first referred to.
+    notes: RefCell<Vec<Note>>,
+    /// Texts of named footnotes, collected in every pass.
+    note_bodies: RefCell<HashMap<Id, DynComponent<InlineComponent>>>,
+    /// Keys of the cited works, in the order they were first cited.
+    citations: RefCell<Vec<String>>,
+    /// The first error reported by a component.
+    error: RefCell<Option<anyhow::Error>>,
+    /// The asset files of the site, if checked.
+    assets: Option<Assets>,
+    /// Images of the assets used by the page.
+    images: RefCell<BTreeSet<InternalPath>>,
+    /// Style sheets required by components, written in the head of the page.
+    /// Collected in every pass, so that the final pass knows all of them.
+    styles: RefCell<Vec<&'static str>>,
+    /// Scripts required by components, by path, written in the head of the
+    /// page. Collected in every pass, like styles.
+    scripts: RefCell<BTreeMap<&'static str, &'static str>>,
+    /// Fonts of the assets required by components, by family name, declared
+    /// in the head of the page. Collected in every pass, like styles.
+    fonts: RefCell<BTreeMap<Id, InternalPath>>,
+    /// Names of the templates being expanded, the innermost last.
+    templates: RefCell<Vec<String>>,
+}
+
+impl PageState {
+    /// Creates the state of a page rendering that may check the asset files.
+    pub(crate) fn with_assets(assets: Option<Assets>) -> Self {
+        Self { assets, ..Self::default() }
+    }
+
+    /// The asset files of the site, if checked.
+    pub(crate) fn assets(&self) -> Option<&Assets> {
+        self.assets.as_ref()
+    }
+
+    /// Requires a style sheet to be included in the page, once.
+    pub(crate) fn require_style(&self, style: &'static str) {
+        let mut styles = self.styles.borrow_mut();
+        if !styles.contains(&style) {
+            styles.push(style);
+        }
+    }
+
+    /// Style sheets required by components.
+    pub(crate) fn styles(&self) -> Vec<&'static str> {
+        self.styles.borrow().clone()
+    }
+
+    /// Requires a script file with the given path and source to be included
+    /// in the page, once.
+    pub(crate) fn require_script(
+        &self,
+        path: &'static str,
+        source: &'static str,
+    ) {
+        self.scripts.borrow_mut().insert(path, source);
+    }
+
+    /// Script files required by components, by path.
+    pub(crate) fn scripts(&self) -> BTreeMap<&'static str, &'static str> {
+        self.scripts.borrow().clone()
+    }
+
+    /// Requires a font file of the assets to be declared in the page under
+    /// the given family name, once.
+    pub(crate) fn require_font(&self, family: &Id, path: &InternalPath) {
+        self.fonts.borrow_mut().insert(family.clone(), path.clone());
+    }
+
+    /// Font files required by components, by family name.
+    pub(crate) fn fonts(&self) -> BTreeMap<Id, InternalPath> {
+        self.fonts.borrow().clone()
+    }
+
+    /// Records that an image of the assets is used by the page.
+    pub(crate) fn use_image(&self, path: &InternalPath) {
+        self.images.borrow_mut().insert(path.clone());
+    }
+
+    /// Images of the assets used by the page.
+    pub(crate) fn images(&self) -> BTreeSet<InternalPath> {
+        self.images.borrow().clone()
+    }
+
+    /// Starts expanding a template. Fails if the template is already being
+    /// expanded, that is, if it transcludes itself.
+    pub(crate) fn enter_template(&self, name: &str) -> anyhow::Result<()> {
+        let mut templates = self.templates.borrow_mut();
+        if let Some(start) = templates.iter().position(|other| other == name) {
+            let mut cycle = templates[start ..].to_vec();
+            cycle.push(String::from(name));
+            Err(anyhow!(
+                "Template {} transcludes itself: {}",
+                name,
+                cycle.join(" -> ")
+            ))?;
+        }
+        templates.push(String::from(name));
+        Ok(())
+    }
+
+    /// Finishes expanding the innermost template.
+    pub(crate) fn leave_template(&self) {
+        self.templates.borrow_mut().pop();
+    }
+
+    /// Tests if this is the final pass.
+    pub(crate) fn is_final_pass(&self) -> bool {
+        self.final_pass.get()
+    }
+
+    /// Resets the counters for the final pass. Collected numbers are kept.
+    pub(crate) fn start_final_pass(&self) {
+        self.final_pass.set(true);
+        self.counters.borrow_mut().clear();
+        self.notes.borrow_mut().clear();
+        self.citations.borrow_mut().clear();
+    }
+
+    /// Records an error found while rendering. Only the first one is kept.
+    pub(crate) fn report(&self, error: anyhow::Error) {
+        self.error.borrow_mut().get_or_insert(error);
+    }
+
+    /// Takes the error reported while rendering, if any.
+    pub(crate) fn take_error(&self) -> Option<anyhow::Error> {
+        self.error.borrow_mut().take()
+    }
+
+    /// Cites a work by its key. Returns the number of the work, in the order
+    /// of first citation, starting at 1.
+    pub(crate) fn cite(&self, key: &str) -> u32 {
+        let mut citations = self.citations.borrow_mut();
+        let index = match citations.iter().position(|cited| cited == key) {
+            Some(index) => index,
+            None => {
+                citations.push(key.to_owned());
+                citations.len() - 1
+            },
+        };
+        index as u32 + 1
+    }
+
+    /// Tests if any work was cited.
+    pub(crate) fn has_citations(&self) -> bool {
+        !self.citations.borrow().is_empty()
+    }
+
+    /// Keys of the cited works, in the order they were first cited.
+    pub(crate) fn citations(&self) -> Vec<String> {
+        self.citations.borrow().clone()
+    }
+
+    /// Takes the next number of the given counter, starting at 1.
+    pub(crate) fn next_number(
+        &self,
+        counter: &'static str,
+        id: Option<&Id>,
+    ) -> u32 {
+        let mut counters = self.counters.borrow_mut();
+        let number = counters.entry(counter).or_insert(0);
+        *number += 1;
+        if let Some(id) = id {
+            self.numbers.borrow_mut().insert(id.clone(), *number);
+        }
+        *number
+    }
+
+    /// Refers to a footnote, possibly named, possibly with its text. Returns
+    /// the number of the note and how many times it was referred to.
+    pub(crate) fn add_note(
+        &self,
+        name: Option<&Id>,
+        body: Option<&DynComponent<InlineComponent>>,
+    ) -> (u32, u32) {
+        if let (Some(name), Some(body)) = (name, body) {
+            self.note_bodies.borrow_mut().insert(name.clone(), body.clone());
+        }
+
+        let mut notes = self.notes.borrow_mut();
+        let existing = name.and_then(|name| {
+            notes.iter().position(|note| note.name.as_ref() == Some(name))
+        });
+        let index = match existing {
+            Some(index) => {
+                let note = &mut notes[index];
+                note.refs += 1;
+                if note.body.is_none() {
+                    note.body = body.cloned();
+                }
+                index
+            },
+            None => {
+                notes.push(Note {
+                    name: name.cloned(),
+                    body: body.cloned(),
+                    refs: 1,
+                });
+                notes.len() - 1
+            },
+        };
+        (index as u32 + 1, notes[index].refs)
+    }
+
+    /// Tests if the text of a named footnote was given anywhere in the page.
+    pub(crate) fn has_note_body(&self, name: &Id) -> bool {
+        self.note_bodies.borrow().contains_key(name)
+    }
+
+    /// Tests if any footnote was referred to.
+    pub(crate) fn has_notes(&self) -> bool {
+        !self.notes.borrow().is_empty()
+    }
+
+    /// The footnote at the given index, with its text resolved.
+    pub(crate) fn note(&self, index: usize) -> Option<Note> {
+        let mut note = self.notes.borrow().get(index)?.clone();
+        if note.body.is_none() {
+            note.body = note
+                .name
+                .as_ref()
+                .and_then(|name| self.note_bodies.borrow().get(name).cloned());
+        }
+        Some(note)
+    }
+
+    /// Finds the number given to an ID in this page.
+    pub(crate) fn number_of(&self, id: &Id) -> Option<u32> {
+        self.numbers.borrow().get(id).copied()
+    }
+
+    /// Finds the number given to an ID in another page, rendering that page
+    /// once in order to collect them. Only done in the final pass, so that
+    /// pages referring to each other do not recurse forever.
+    pub(crate) fn foreign_number_of(
+        &self,
+        path: &InternalPath,
+        id: &Id,
+        site: &Site,
+    ) -> Option<u32> {
+        if !self.final_pass.get() {
+            return None;
+        }
+
+        if let Some(numbers) = self.foreign_numbers.borrow().get(path) {
+            return numbers.get(id).copied();
+        }
+
+        let (location, page) = match site.root.get(path.clone())? {
+            Node::Page(page) => (path.clone(), page),
+            Node::Directory(dir) => {
+                let index = Fragment::new("index.html").ok()?;
+                let page = dir.contents.get(&index)?.as_ref().page()?;
+                (path.clone().append(index), page)
+            },
+        };
+        let render = RenderPage { page, location: &location, site };
+        let state = PageState::default();
+        write!(Sink, "{}", RenderPass { page: &render, state: &state }).ok()?;
+
+        let number = state.number_of(id);
+        self.foreign_numbers
+            .borrow_mut()
+            .insert(path.clone(), state.numbers.into_inner());
+        number
+    }
 }
 
 fn heading_level(section_level: u32) -> &'static str {
@@ -145,3 +638,202 @@ fn heading_level(section_level: u32) -> &'static str {
         _ => "h6",
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{Page, RenderPage, Section};
+    use crate::{
+        component::{
+            cite::Cite,
+            math::{Equation, EquationRef, Math},
+            note::Footnote,
+            text::Paragraph,
+            Component,
+        },
+        location::{Id, InternalLoc, InternalPath},
+        site::Site,
+    };
+
+    #[test]
+    fn forward_reference() {
+        let site = Site::default();
+        let location = InternalPath::parse("astronomy/index.html").unwrap();
+        let page = Page {
+            title: String::from("Astronomy"),
+            body: vec![
+                Paragraph(EquationRef(
+                    InternalLoc::parse("astronomy#year").unwrap(),
+                ))
+                .to_dyn(),
+                Equation::new(Math::new("x").unwrap()).to_dyn(),
+                Equation::numbered(
+                    Math::new("y = 365").unwrap(),
+                    Id::new("year").unwrap(),
+                )
+                .to_dyn(),
+            ]
+            .to_dyn(),
+            sections: Vec::new(),
+            lang: None,
+        };
+        let mut html = Vec::new();
+        RenderPage { page: &page, location: &location, site: &site }
+            .write_to(&mut html)
+            .unwrap();
+        let html = String::from_utf8(html).unwrap();
+        assert!(
+            html.contains("<a href=\"#year\" class=\"equation-ref\">(1)</a>")
+        );
+        assert!(html.contains("<span class=\"equation-number\">(1)</span>"));
+    }
+
+    #[test]
+    fn page_lang_overrides_site() {
+        let site = Site { lang: Some(String::from("en")), ..Site::default() };
+        let location = InternalPath::parse("langs/div/index.html").unwrap();
+        let mut page = Page {
+            title: String::from("Divine"),
+            body: Paragraph("Lupus").to_dyn(),
+            sections: Vec::new(),
+            lang: None,
+        };
+        let render = |page: &Page| {
+            let mut html = Vec::new();
+            RenderPage { page, location: &location, site: &site }
+                .write_to(&mut html)
+                .unwrap();
+            String::from_utf8(html).unwrap()
+        };
+        assert!(render(&page).starts_with("<!DOCTYPE html><html lang=\"en\">"));
+        page.lang = Some(String::from("art-x-div"));
+        assert!(render(&page)
+            .starts_with("<!DOCTYPE html><html lang=\"art-x-div\">"));
+    }
+
+    #[test]
+    fn footnotes_across_sections() {
+        let site = Site::default();
+        let location = InternalPath::parse("langs/index.html").unwrap();
+        let source = Id::new("source").unwrap();
+        let section = |id: &str, body: Footnote, children| Section {
+            title: String::from(id).to_dyn(),
+            body: Paragraph(body).to_dyn(),
+            id: Id::new(id).unwrap(),
+            children,
+        };
+        let page = Page {
+            title: String::from("Languages"),
+            body: Paragraph(Footnote::reuse(source.clone())).to_dyn(),
+            sections: vec![section(
+                "first",
+                Footnote::new("plain"),
+                vec![section(
+                    "nested",
+                    Footnote::named(source.clone(), "Grammar"),
+                    Vec::new(),
+                )],
+            )],
+            lang: None,
+        };
+        let mut html = Vec::new();
+        RenderPage { page: &page, location: &location, site: &site }
+            .write_to(&mut html)
+            .unwrap();
+        let html = String::from_utf8(html).unwrap();
+
+        assert!(html.contains("id=\"fnref-1\"><a href=\"#fn-1\">[1]</a>"));
+        assert!(html.contains("id=\"fnref-2\"><a href=\"#fn-2\">[2]</a>"));
+        assert!(html.contains("id=\"fnref-1-2\"><a href=\"#fn-1\">[1]</a>"));
+        assert!(html.contains(
+            "<li id=\"fn-1\" class=\"footnote\">Grammar <a href=\"#fnref-1\" \
+             class=\"footnote-back\">↑</a> <a href=\"#fnref-1-2\" \
+             class=\"footnote-back\">↑</a></li><li id=\"fn-2\" \
+             class=\"footnote\">plain"
+        ));
+    }
+
+    #[test]
+    fn unknown_citation_fails() {
+        let site = Site::default();
+        let location = InternalPath::parse("langs/index.html").unwrap();
+        let page = Page {
+            title: String::from("Languages"),
+            body: Paragraph(Cite::new("nobody1999")).to_dyn(),
+            sections: Vec::new(),
+            lang: None,
+        };
+        let render =
+            RenderPage { page: &page, location: &location, site: &site };
+        assert_eq!(
+            render.to_string(),
+            "Rendering page langs/index.html: Unknown citation key nobody1999"
+        );
+        let error = render.write_to(Vec::new()).unwrap_err();
+        assert_eq!(error.to_string(), "Rendering page langs/index.html");
+        assert_eq!(
+            error.root_cause().to_string(),
+            "Unknown citation key nobody1999"
+        );
+    }
+
+    #[test]
+    fn unknown_equation_fails() {
+        let site = Site::default();
+        let location = InternalPath::parse("langs/index.html").unwrap();
+        let page = Page {
+            title: String::from("Languages"),
+            body: Paragraph(EquationRef(
+                InternalLoc::parse("langs#nowhere").unwrap(),
+            ))
+            .to_dyn(),
+            sections: Vec::new(),
+            lang: None,
+        };
+        let error =
+            RenderPage { page: &page, location: &location, site: &site }
+                .write_to(Vec::new())
+                .unwrap_err();
+        assert_eq!(
+            error.root_cause().to_string(),
+            "Unknown equation langs#nowhere"
+        );
+    }
+
+    #[test]
+    fn reserved_and_unknown_notes() {
+        let site = Site::default();
+        let location = InternalPath::parse("langs/index.html").unwrap();
+        let render = |page: &Page| {
+            RenderPage { page, location: &location, site: &site }
+                .write_to(Vec::new())
+                .unwrap_err()
+                .root_cause()
+                .to_string()
+        };
+
+        let page = Page {
+            title: String::from("Languages"),
+            body: Paragraph(Footnote::reuse(Id::new("nowhere").unwrap()))
+                .to_dyn(),
+            sections: Vec::new(),
+            lang: None,
+        };
+        assert_eq!(render(&page), "Unknown footnote nowhere");
+
+        let page = Page {
+            title: String::from("Languages"),
+            body: Paragraph(Footnote::new("plain")).to_dyn(),
+            sections: vec![Section {
+                title: String::from("Notes").to_dyn(),
+                body: Paragraph("Mine").to_dyn(),
+                id: Id::new("notes").unwrap(),
+                children: Vec::new(),
+            }],
+            lang: None,
+        };
+        assert_eq!(
+            render(&page),
+            "Section ID notes is reserved for the generated Notes section"
+        );
+    }
+}
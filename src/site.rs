@@ -2,14 +2,20 @@
 //! whole.
 
 use crate::{
+    asset::Assets,
+    bib::Bibliography,
+    component::{glossary::GlossaryList, Component, Context},
+    glossary::{link_terms, Glossary},
+    lexicon::Lexicon,
     location::{Fragment, InternalPath},
-    page::{Page, RenderPage},
+    page::{Page, PageState, RenderPage},
+    template::Templates,
 };
-use anyhow::Context as _;
+use anyhow::{anyhow, Context as _};
 use std::{
-    collections::{hash_map, HashMap},
+    collections::{hash_map, BTreeMap, BTreeSet, HashMap, HashSet},
     fs,
-    io::{BufWriter, Write},
+    io::{BufWriter, Write as _},
     path::PathBuf,
 };
 
@@ -199,10 +205,21 @@ impl<'dir> IntoIterator for &'dir Directory {
 }
 
 /// The whole encyclopedia's (sub)site.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct Site {
     /// The root directory of the site.
     pub root: Directory,
+    /// The works that pages can cite.
+    pub bibliography: Bibliography,
+    /// Named templates that pages can use.
+    pub templates: Templates,
+    /// Terms and abbreviations that pages can refer to.
+    pub glossary: Glossary,
+    /// Dictionaries whose pages are generated along with the site.
+    pub lexicons: Vec<Lexicon>,
+    /// The language of the pages, such as `en`, set on their `<html>`
+    /// element unless a page sets its own.
+    pub lang: Option<String>,
 }
 
 /// Iterator over the pages of a directory.
@@ -244,16 +261,80 @@ pub struct Generator {
     pub assets_dir: PathBuf,
     /// The output directory of the HTML/CSS/JS site/web pages.
     pub output_dir: PathBuf,
+    /// Widths in pixels of the thumbnails generated for every image used by
+    /// the pages and wider than them.
+    pub thumbnail_widths: Vec<u32>,
+    /// Directory where generated thumbnails are cached between generations,
+    /// by the contents of their image. Even if `None`, thumbnails already in
+    /// the output directory are kept while newer than their image.
+    pub cache_dir: Option<PathBuf>,
+    /// Whether the first occurrence of each glossary term is linked in every
+    /// page.
+    pub link_glossary: bool,
+    /// Where an alphabetical page of the glossary is generated. Not generated
+    /// if `None`.
+    pub glossary_page: Option<InternalPath>,
 }
 
 impl Generator {
+    /// Creates a generator of the given site, with the given directories of
+    /// assets and output. No thumbnail is generated, and no cache directory
+    /// is used.
+    pub fn new<A, O>(site: Site, assets_dir: A, output_dir: O) -> Self
+    where
+        A: Into<PathBuf>,
+        O: Into<PathBuf>,
+    {
+        Self {
+            site,
+            assets_dir: assets_dir.into(),
+            output_dir: output_dir.into(),
+            thumbnail_widths: Vec::new(),
+            cache_dir: None,
+            link_glossary: false,
+            glossary_page: None,
+        }
+    }
+
+    /// Sets the widths in pixels of the thumbnails generated for every image
+    /// used by the pages and wider than them.
+    pub fn thumbnail_widths(mut self, widths: Vec<u32>) -> Self {
+        self.thumbnail_widths = widths;
+        self
+    }
+
+    /// Sets the directory where generated thumbnails are cached between
+    /// generations.
+    pub fn cache_dir<P>(mut self, dir: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        self.cache_dir = Some(dir.into());
+        self
+    }
+
+    /// Links the first occurrence of each glossary term in every page.
+    pub fn link_glossary(mut self) -> Self {
+        self.link_glossary = true;
+        self
+    }
+
+    /// Sets where an alphabetical page of the glossary is generated.
+    pub fn glossary_page(mut self, path: InternalPath) -> Self {
+        self.glossary_page = Some(path);
+        self
+    }
+
     /// Takes a [`Site`] and transforms it into an actual HTML/CSS/JS web pages.
-    /// Also copies assets.
+    /// Also copies assets and generates thumbnails of the used images.
     pub fn gen(&self) -> anyhow::Result<()> {
         if self.assets_dir != self.output_dir {
             self.copy_assets()?;
         }
-        self.gen_pages()?;
+        let assets = self.assets();
+        let uses = self.gen_pages(&assets)?;
+        self.gen_thumbnails(&assets, &uses.images)?;
+        self.gen_scripts(&uses.scripts)?;
 
         Ok(())
     }
@@ -302,27 +383,288 @@ impl Generator {
         Ok(())
     }
 
-    /// Generate the pages. Internal use.
-    fn gen_pages(&self) -> anyhow::Result<()> {
+    /// The asset files, as seen while rendering pages. Internal use.
+    fn assets(&self) -> Assets {
+        Assets::new(self.assets_dir.clone())
+            .thumbnail_widths(self.thumbnail_widths.clone())
+    }
+
+    /// Generate the pages, including the glossary page and the pages of the
+    /// lexicons. Returns what they use. Internal use.
+    fn gen_pages(&self, assets: &Assets) -> anyhow::Result<PageUses> {
+        let mut generated = Vec::new();
+        if let Some(loc) = &self.glossary_page {
+            let page = Page {
+                title: String::from("Glossary"),
+                body: GlossaryList.to_dyn(),
+                sections: Vec::new(),
+                lang: None,
+            };
+            generated.push((loc.clone(), page));
+        }
+        for lexicon in &self.site.lexicons {
+            let pages = lexicon.pages().with_context(|| {
+                format!("Generating lexicon {}", lexicon.title)
+            })?;
+            generated.extend(pages);
+        }
+
+        let mut paths = HashSet::new();
+        for (loc, _) in &generated {
+            if self.site.root.get(loc.clone()).is_some() || !paths.insert(loc) {
+                Err(anyhow!(
+                    "Generated page {} clashes with another page",
+                    loc
+                ))?;
+            }
+        }
+        let mut uses = PageUses::default();
         for (loc, page) in &self.site.root {
-            let path = self.output_dir.join(loc.to_fs_path());
+            self.gen_page(&loc, page, assets, &mut uses)?;
+        }
+        for (loc, page) in &generated {
+            self.gen_page(loc, page, assets, &mut uses)?;
+        }
+
+        Ok(uses)
+    }
+
+    /// Generate a single page, recording what it uses. Internal use.
+    fn gen_page(
+        &self,
+        loc: &InternalPath,
+        page: &Page,
+        assets: &Assets,
+        uses: &mut PageUses,
+    ) -> anyhow::Result<()> {
+        let path = self.output_dir.join(loc.to_fs_path());
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).with_context(|| {
+                format!("Creating dir {}", parent.display())
+            })?;
+        }
+
+        let mut html = Vec::new();
+        let res = RenderPage { page, location: loc, site: &self.site }
+            .write_state(&mut html, Some(assets));
+        let state =
+            res.with_context(|| format!("Generating page {}", path.display()))?;
+        uses.images.extend(state.images());
+        uses.scripts.extend(state.scripts());
+
+        let mut html = String::from_utf8(html)?;
+        if self.link_glossary && self.glossary_page.as_ref() != Some(loc) {
+            let state = PageState::default();
+            let ctx = Context::new(loc, &self.site, &state);
+            html = link_terms(&html, ctx, self.glossary_page.as_ref());
+        }
+
+        let file = fs::File::create(&path).with_context(|| {
+            format!("Creating page file {}", path.display())
+        })?;
+        let mut output = BufWriter::new(file);
+        output
+            .write_all(html.as_bytes())
+            .and_then(|()| output.flush())
+            .with_context(|| format!("Writing page file {}", path.display()))?;
+        Ok(())
+    }
+
+    /// Writes the script files required by the pages. Internal use.
+    fn gen_scripts(
+        &self,
+        scripts: &BTreeMap<&'static str, &'static str>,
+    ) -> anyhow::Result<()> {
+        for (path, source) in scripts {
+            let path = self.output_dir.join(path);
             if let Some(parent) = path.parent() {
                 fs::create_dir_all(parent).with_context(|| {
                     format!("Creating dir {}", parent.display())
                 })?;
             }
-
-            let file = fs::File::create(&path).with_context(|| {
-                format!("Creating page file {}", path.display())
+            fs::write(&path, source).with_context(|| {
+                format!("Writing script {}", path.display())
             })?;
-            let res = write!(
-                BufWriter::new(file),
-                "{}",
-                RenderPage { page, location: &loc, site: &self.site }
-            );
-            res.with_context(|| format!("Generating page {}", path.display()))?;
         }
+        Ok(())
+    }
 
+    /// Generates the thumbnails of the given images. Internal use.
+    fn gen_thumbnails(
+        &self,
+        assets: &Assets,
+        images: &BTreeSet<InternalPath>,
+    ) -> anyhow::Result<()> {
+        for image in images {
+            assets
+                .gen_thumbnails(
+                    image,
+                    &self.output_dir,
+                    self.cache_dir.as_deref(),
+                )
+                .with_context(|| {
+                    format!("Generating thumbnails of {}", image)
+                })?;
+        }
         Ok(())
     }
 }
+
+/// What the generated pages use, besides their contents. Internal use.
+#[derive(Debug, Clone, Default)]
+struct PageUses {
+    /// Images of the assets.
+    images: BTreeSet<InternalPath>,
+    /// Script files, by path.
+    scripts: BTreeMap<&'static str, &'static str>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Generator, Node, Site};
+    use crate::{
+        component::{
+            img::Image,
+            table::{Entry, Table},
+            text::Paragraph,
+            Component,
+        },
+        glossary::GlossaryEntry,
+        location::{InternalPath, Location},
+        page::Page,
+    };
+    use std::{
+        fs,
+        time::{Duration, SystemTime},
+    };
+
+    #[test]
+    fn generated_page_clash() {
+        let mut site = Site::default();
+        site.root.insert(
+            InternalPath::parse("glossary.html").unwrap(),
+            Node::Page(Page {
+                title: String::from("Glossary"),
+                body: Paragraph("Terms").to_dyn(),
+                sections: Vec::new(),
+                lang: None,
+            }),
+        );
+        let temp = tempfile::tempdir().unwrap();
+        let assets_dir = temp.path().join("assets");
+        fs::create_dir_all(&assets_dir).unwrap();
+        let result = Generator::new(site, &assets_dir, temp.path().join("out"))
+            .glossary_page(InternalPath::parse("glossary.html").unwrap())
+            .gen();
+        assert_eq!(
+            format!("{:#}", result.unwrap_err()),
+            "Generated page glossary.html clashes with another page"
+        );
+    }
+
+    #[test]
+    fn invalid_table_page() {
+        let mut site = Site::default();
+        site.root.insert(
+            InternalPath::parse("index.html").unwrap(),
+            Node::Page(Page {
+                title: String::from("Nouns"),
+                body: Table::new(
+                    "Nouns",
+                    vec![vec![Entry { colspan: 0, ..Entry::new("lupus") }]],
+                )
+                .to_dyn(),
+                sections: Vec::new(),
+                lang: None,
+            }),
+        );
+        let temp = tempfile::tempdir().unwrap();
+        let assets_dir = temp.path().join("assets");
+        fs::create_dir_all(&assets_dir).unwrap();
+        let result =
+            Generator::new(site, &assets_dir, temp.path().join("out")).gen();
+        assert!(format!("{:#}", result.unwrap_err()).contains(
+            "Invalid table: Entry at row 0, column 0 has a zero span"
+        ));
+    }
+
+    #[test]
+    fn thumbnails_kept_by_default() {
+        let mut site = Site::default();
+        site.root.insert(
+            InternalPath::parse("index.html").unwrap(),
+            Node::Page(Page {
+                title: String::from("Map"),
+                body: Image {
+                    src: Location::internal("img/map.png"),
+                    alt: String::from("Map"),
+                }
+                .to_dyn(),
+                sections: Vec::new(),
+                lang: None,
+            }),
+        );
+        let temp = tempfile::tempdir().unwrap();
+        let assets_dir = temp.path().join("assets");
+        let output_dir = temp.path().join("output");
+        fs::create_dir_all(assets_dir.join("img")).unwrap();
+        let image = assets_dir.join("img/map.png");
+        image::RgbImage::new(8, 4).save(&image).unwrap();
+        let generator = Generator::new(site, &assets_dir, &output_dir)
+            .thumbnail_widths(vec![4]);
+
+        generator.gen().unwrap();
+        let thumbnail = output_dir.join("img/map-4w.png");
+        assert_eq!(image::image_dimensions(&thumbnail).unwrap(), (4, 2));
+
+        fs::write(&thumbnail, "kept").unwrap();
+        generator.gen().unwrap();
+        assert_eq!(fs::read_to_string(&thumbnail).unwrap(), "kept");
+
+        fs::File::options()
+            .write(true)
+            .open(&image)
+            .unwrap()
+            .set_modified(SystemTime::now() + Duration::from_secs(60))
+            .unwrap();
+        generator.gen().unwrap();
+        assert_eq!(image::image_dimensions(&thumbnail).unwrap(), (4, 2));
+    }
+
+    #[test]
+    fn glossary_page() {
+        let mut site = Site::default();
+        site.glossary.insert("NOM", GlossaryEntry::new("nominative"));
+        site.root.insert(
+            InternalPath::parse("langs/index.html").unwrap(),
+            Node::Page(Page {
+                title: String::from("Languages"),
+                body: Paragraph("The NOM case").to_dyn(),
+                sections: Vec::new(),
+                lang: None,
+            }),
+        );
+        let temp = tempfile::tempdir().unwrap();
+        let assets_dir = temp.path().join("assets");
+        let output_dir = temp.path().join("output");
+        fs::create_dir_all(&assets_dir).unwrap();
+        Generator::new(site, &assets_dir, &output_dir)
+            .link_glossary()
+            .glossary_page(InternalPath::parse("glossary.html").unwrap())
+            .gen()
+            .unwrap();
+
+        let glossary =
+            fs::read_to_string(output_dir.join("glossary.html")).unwrap();
+        assert!(glossary.contains(
+            "<dl class=\"definition-list glossary\"><dt \
+             id=\"nom\">NOM</dt><dd>nominative</dd></dl>"
+        ));
+        let page =
+            fs::read_to_string(output_dir.join("langs/index.html")).unwrap();
+        assert!(page.contains(
+            "<p class=\"paragraph\">The <a href=\"../glossary.html#nom\" \
+             title=\"nominative\" class=\"glossary-link\">NOM</a> case</p>"
+        ));
+    }
+}
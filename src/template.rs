@@ -0,0 +1,186 @@
+//! This module provides the registry of templates of the site: named,
+//! parameterized components shared across pages, such as navigation boxes.
+//! Pages use them through
+//! [`Transclude`](crate::component::template::Transclude).
+
+use crate::component::{
+    BlockComponent,
+    Component,
+    DynComponent,
+    InlineComponent,
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    sync::Arc,
+};
+use thiserror::Error;
+
+/// An error found while instantiating a template.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum TemplateError {
+    /// No template was registered with the given name.
+    #[error("Unknown template {0}")]
+    Unknown(String),
+    /// The template does not declare the given parameter.
+    #[error("Unknown parameter {param} of template {template}")]
+    UnknownParam {
+        /// Name of the template.
+        template: String,
+        /// Name of the parameter.
+        param: String,
+    },
+    /// A required parameter of the template was not given.
+    #[error("Missing parameter {param} of template {template}")]
+    MissingParam {
+        /// Name of the template.
+        template: String,
+        /// Name of the parameter.
+        param: String,
+    },
+}
+
+/// The function building the contents of a template from its arguments.
+type TemplateBody =
+    Arc<dyn Fn(&TemplateArgs) -> DynComponent<BlockComponent> + Send + Sync>;
+
+/// A parameterized component definition.
+#[derive(Clone)]
+pub struct Template {
+    /// Declared parameters, and whether they are required.
+    params: Vec<(String, bool)>,
+    /// Builds the contents from the arguments.
+    body: TemplateBody,
+}
+
+impl Template {
+    /// Creates a template without parameters, whose contents are built from
+    /// the arguments by the given function.
+    pub fn new<F>(body: F) -> Self
+    where
+        F: Fn(&TemplateArgs) -> DynComponent<BlockComponent>
+            + Send
+            + Sync
+            + 'static,
+    {
+        Self { params: Vec::new(), body: Arc::new(body) }
+    }
+
+    /// Declares a required parameter.
+    pub fn param<S>(mut self, name: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.params.push((name.into(), true));
+        self
+    }
+
+    /// Declares an optional parameter.
+    pub fn optional<S>(mut self, name: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.params.push((name.into(), false));
+        self
+    }
+
+    /// Checks the arguments against the declared parameters and builds the
+    /// contents. The name is only used in errors.
+    pub fn instantiate(
+        &self,
+        name: &str,
+        args: &TemplateArgs,
+    ) -> Result<DynComponent<BlockComponent>, TemplateError> {
+        for param in args.args.keys() {
+            if !self.params.iter().any(|(declared, _)| declared == param) {
+                Err(TemplateError::UnknownParam {
+                    template: name.to_owned(),
+                    param: param.clone(),
+                })?;
+            }
+        }
+        for (param, required) in &self.params {
+            if *required && !args.args.contains_key(param) {
+                Err(TemplateError::MissingParam {
+                    template: name.to_owned(),
+                    param: param.clone(),
+                })?;
+            }
+        }
+        Ok((self.body)(args))
+    }
+}
+
+impl fmt::Debug for Template {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("Template")
+            .field("params", &self.params)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Arguments given to a template, by parameter name.
+#[derive(Debug, Clone, Default)]
+pub struct TemplateArgs {
+    args: BTreeMap<String, DynComponent<InlineComponent>>,
+}
+
+impl TemplateArgs {
+    /// Creates an empty list of arguments.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the argument of the given parameter.
+    pub fn arg<S, T>(mut self, param: S, value: T) -> Self
+    where
+        S: Into<String>,
+        T: Component<Kind = InlineComponent> + Send + Sync + 'static,
+    {
+        self.args.insert(param.into(), value.to_dyn());
+        self
+    }
+
+    /// The argument of the given parameter, if given.
+    pub fn get(&self, param: &str) -> Option<&DynComponent<InlineComponent>> {
+        self.args.get(param)
+    }
+}
+
+/// The site-wide registry of templates, indexed by name.
+#[derive(Debug, Clone, Default)]
+pub struct Templates {
+    templates: HashMap<String, Template>,
+}
+
+impl Templates {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a template under the given name, replacing any previous one.
+    pub fn insert<S>(&mut self, name: S, template: Template)
+    where
+        S: Into<String>,
+    {
+        self.templates.insert(name.into(), template);
+    }
+
+    /// Finds a template by its name.
+    pub fn get(&self, name: &str) -> Option<&Template> {
+        self.templates.get(name)
+    }
+
+    /// Finds a template by its name and instantiates it with the given
+    /// arguments.
+    pub fn instantiate(
+        &self,
+        name: &str,
+        args: &TemplateArgs,
+    ) -> Result<DynComponent<BlockComponent>, TemplateError> {
+        self.get(name)
+            .ok_or_else(|| TemplateError::Unknown(name.to_owned()))?
+            .instantiate(name, args)
+    }
+}